@@ -0,0 +1,460 @@
+use crate::cli::{storage, template};
+use crate::dependencies::spec;
+use crate::types::{
+    CliToolTemplate, ConflictResolution, TemplateBundle, TemplateBundleMetadata,
+    TemplateImportConflict, TemplateImportPreview, TemplateImportResult,
+};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Current bundle file format version
+pub const BUNDLE_SCHEMA_VERSION: &str = "1.0";
+
+/// Build a bundle from the given custom templates
+pub fn build_bundle(
+    ids: &[String],
+    custom_templates: &[CliToolTemplate],
+    author: Option<String>,
+    version: Option<String>,
+) -> Result<TemplateBundle, String> {
+    if ids.is_empty() {
+        return Err("No templates selected for export".to_string());
+    }
+
+    let mut templates = Vec::new();
+    for id in ids {
+        let template = custom_templates
+            .iter()
+            .find(|t| &t.id == id)
+            .ok_or_else(|| format!("Custom template '{}' not found", id))?;
+        templates.push(template.clone());
+    }
+
+    Ok(TemplateBundle {
+        schema_version: BUNDLE_SCHEMA_VERSION.to_string(),
+        metadata: TemplateBundleMetadata {
+            author: author.filter(|a| !a.is_empty()),
+            created: chrono::Utc::now().to_rfc3339(),
+            version: version
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "1.0.0".to_string()),
+        },
+        templates,
+    })
+}
+
+/// Export custom templates as a single bundle file in the download directory
+///
+/// # Returns
+/// * `Ok(PathBuf)` with the path of the written bundle file
+/// * `Err(String)` if a template is unknown or the file can't be written
+pub fn export_templates(
+    ids: &[String],
+    author: Option<String>,
+    version: Option<String>,
+) -> Result<PathBuf, String> {
+    let custom = storage::load_custom_templates()?;
+    let bundle = build_bundle(ids, &custom, author, version)?;
+
+    let download_dir = crate::installation::download::get_download_dir()
+        .map_err(|e| format!("Failed to get download directory: {}", e))?;
+    let timestamp = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let file_path = unused_path(&download_dir, &format!("dev-env-templates-{}", timestamp), "json");
+
+    let json = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+
+    fs::write(&file_path, json)
+        .map_err(|e| format!("Failed to write bundle file {}: {}", file_path.display(), e))?;
+
+    Ok(file_path)
+}
+
+/// Pick `<stem>.<ext>` in `dir`, or `<stem>-2.<ext>`, ... if it already exists
+fn unused_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let first = dir.join(format!("{}.{}", stem, extension));
+    if !first.exists() {
+        return first;
+    }
+    (2..)
+        .map(|n| dir.join(format!("{}-{}.{}", stem, n, extension)))
+        .find(|path| !path.exists())
+        .unwrap_or(first)
+}
+
+/// Read a bundle file and validate every template in it
+pub fn read_bundle(path: &str) -> Result<TemplateBundle, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read bundle file: {}", e))?;
+
+    let bundle: TemplateBundle = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse bundle file: {}", e))?;
+
+    validate_bundle(&bundle)?;
+
+    Ok(bundle)
+}
+
+/// Validate bundle format version, template shape and duplicate IDs
+fn validate_bundle(bundle: &TemplateBundle) -> Result<(), String> {
+    let major = bundle.schema_version.split('.').next().unwrap_or("");
+    let supported_major = BUNDLE_SCHEMA_VERSION.split('.').next().unwrap_or("");
+    if major != supported_major {
+        return Err(format!(
+            "Unsupported bundle version {} (expected {})",
+            bundle.schema_version, BUNDLE_SCHEMA_VERSION
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for template in &bundle.templates {
+        storage::validate_template(template)
+            .map_err(|e| format!("Invalid template '{}': {}", template.id, e))?;
+
+        if !seen.insert(template.id.as_str()) {
+            return Err(format!("Duplicate template ID in bundle: {}", template.id));
+        }
+    }
+
+    Ok(())
+}
+
+/// Preview the import of a bundle file against the installed templates
+pub fn preview_import(path: &str) -> Result<TemplateImportPreview, String> {
+    let bundle = read_bundle(path)?;
+    let builtins = template::get_builtin_templates();
+    let custom = storage::load_custom_templates()?;
+    Ok(preview_import_with_templates(&bundle, &builtins, &custom))
+}
+
+/// Preview an import with provided templates (for testing)
+pub fn preview_import_with_templates(
+    bundle: &TemplateBundle,
+    builtins: &[CliToolTemplate],
+    custom: &[CliToolTemplate],
+) -> TemplateImportPreview {
+    let existing: HashMap<&str, (&CliToolTemplate, bool)> = builtins
+        .iter()
+        .map(|t| (t.id.as_str(), (t, true)))
+        .chain(custom.iter().map(|t| (t.id.as_str(), (t, false))))
+        .collect();
+
+    let conflicts = bundle
+        .templates
+        .iter()
+        .filter_map(|incoming| {
+            existing
+                .get(incoming.id.as_str())
+                .map(|(current, is_builtin)| TemplateImportConflict {
+                    template_id: incoming.id.clone(),
+                    existing_name: current.name.clone(),
+                    incoming_name: incoming.name.clone(),
+                    is_builtin: *is_builtin,
+                })
+        })
+        .collect();
+
+    TemplateImportPreview {
+        metadata: bundle.metadata.clone(),
+        templates: bundle.templates.clone(),
+        conflicts,
+        missing_dependencies: find_missing_dependencies(bundle, builtins, custom),
+    }
+}
+
+/// Find dependencies that are neither installed templates nor part of the bundle
+/// Returns (template_id, dependency_id) pairs
+fn find_missing_dependencies(
+    bundle: &TemplateBundle,
+    builtins: &[CliToolTemplate],
+    custom: &[CliToolTemplate],
+) -> Vec<(String, String)> {
+    let known: HashSet<&str> = builtins
+        .iter()
+        .chain(custom.iter())
+        .chain(bundle.templates.iter())
+        .map(|t| t.id.as_str())
+        .collect();
+
+    let mut missing = Vec::new();
+    for template in &bundle.templates {
+        for dep_spec in spec::dependency_specs(template) {
+            for dep in dep_spec.ids() {
+                if !known.contains(dep) {
+                    missing.push((template.id.clone(), dep.to_string()));
+                }
+            }
+        }
+    }
+    missing
+}
+
+/// Decide what to write for each template in the bundle
+///
+/// Renamed templates get a fresh ID, and dependencies inside the bundle that
+/// point at a renamed template are rewritten to follow it.
+///
+/// # Returns
+/// * `Ok((templates_to_save, result))` describing the import
+/// * `Err(String)` if dependencies are missing or a built-in would be overwritten
+pub fn plan_import(
+    bundle: &TemplateBundle,
+    builtins: &[CliToolTemplate],
+    custom: &[CliToolTemplate],
+    resolution: ConflictResolution,
+) -> Result<(Vec<CliToolTemplate>, TemplateImportResult), String> {
+    let missing = find_missing_dependencies(bundle, builtins, custom);
+    if let Some((template_id, dep)) = missing.first() {
+        return Err(format!(
+            "Template '{}' depends on unknown tool '{}'",
+            template_id, dep
+        ));
+    }
+
+    let builtin_ids: HashSet<&str> = builtins.iter().map(|t| t.id.as_str()).collect();
+    let custom_ids: HashSet<&str> = custom.iter().map(|t| t.id.as_str()).collect();
+    let mut taken: HashSet<String> = builtins
+        .iter()
+        .chain(custom.iter())
+        .chain(bundle.templates.iter())
+        .map(|t| t.id.clone())
+        .collect();
+
+    let mut result = TemplateImportResult {
+        imported: Vec::new(),
+        overwritten: Vec::new(),
+        renamed: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let mut renames: HashMap<String, String> = HashMap::new();
+    let mut to_save = Vec::new();
+
+    for incoming in &bundle.templates {
+        let is_builtin = builtin_ids.contains(incoming.id.as_str());
+        let conflicts = is_builtin || custom_ids.contains(incoming.id.as_str());
+
+        if !conflicts {
+            result.imported.push(incoming.id.clone());
+            to_save.push(incoming.clone());
+            continue;
+        }
+
+        match resolution {
+            ConflictResolution::Skip => {
+                result.skipped.push(incoming.id.clone());
+            }
+            ConflictResolution::Overwrite => {
+                if is_builtin {
+                    return Err(format!("Cannot override built-in template '{}'", incoming.id));
+                }
+                result.overwritten.push(incoming.id.clone());
+                to_save.push(incoming.clone());
+            }
+            ConflictResolution::Rename => {
+                let new_id = unique_id(&incoming.id, &taken);
+                taken.insert(new_id.clone());
+                renames.insert(incoming.id.clone(), new_id.clone());
+                result.renamed.push((incoming.id.clone(), new_id.clone()));
+
+                let mut renamed = incoming.clone();
+                renamed.id = new_id;
+                to_save.push(renamed);
+            }
+        }
+    }
+
+    for template in &mut to_save {
+        if let Some(deps) = &mut template.dependencies {
+            for dep in deps.iter_mut() {
+                let mut dep_spec = spec::parse_dependency(dep)?;
+                if dep_spec.ids().any(|id| renames.contains_key(id)) {
+                    for alternative in &mut dep_spec.alternatives {
+                        if let Some(new_id) = renames.get(&alternative.id) {
+                            alternative.id = new_id.clone();
+                        }
+                    }
+                    *dep = dep_spec.to_string();
+                }
+            }
+        }
+        storage::validate_template(template)?;
+    }
+
+    Ok((to_save, result))
+}
+
+/// Pick the first free ID of the form `<id>-imported`, `<id>-imported-2`, ...
+fn unique_id(id: &str, taken: &HashSet<String>) -> String {
+    let base = format!("{}-imported", id);
+    if !taken.contains(&base) {
+        return base;
+    }
+
+    let mut n = 2;
+    loop {
+        let candidate = format!("{}-{}", base, n);
+        if !taken.contains(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Import a bundle file into the custom templates directory
+pub fn import_templates(
+    path: &str,
+    resolution: ConflictResolution,
+) -> Result<TemplateImportResult, String> {
+    let bundle = read_bundle(path)?;
+    let builtins = template::get_builtin_templates();
+    let custom = storage::load_custom_templates()?;
+
+    let (to_save, result) = plan_import(&bundle, &builtins, &custom, resolution)?;
+
+    storage::save_custom_templates(&to_save)?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_template(id: &str, dependencies: Option<Vec<&str>>) -> CliToolTemplate {
+        CliToolTemplate {
+            id: id.to_string(),
+            name: format!("Tool {}", id),
+            executable: id.to_string(),
+            version_command: "--version".to_string(),
+            version_parser: "stdout".to_string(),
+            config_files: vec![],
+            install_methods: None,
+            dependencies: dependencies.map(|deps| deps.iter().map(|s| s.to_string()).collect()),
+            category: None,
+            emoji: None,
+        }
+    }
+
+    fn create_test_bundle(templates: Vec<CliToolTemplate>) -> TemplateBundle {
+        TemplateBundle {
+            schema_version: BUNDLE_SCHEMA_VERSION.to_string(),
+            metadata: TemplateBundleMetadata {
+                author: Some("tester".to_string()),
+                created: "2025-01-01T00:00:00Z".to_string(),
+                version: "1.0.0".to_string(),
+            },
+            templates,
+        }
+    }
+
+    #[test]
+    fn test_build_bundle_unknown_id() {
+        let custom = vec![create_test_template("a", None)];
+        let result = build_bundle(&["b".to_string()], &custom, None, None);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_validate_bundle_rejects_unsafe_ids() {
+        for id in ["../../x", "a/b", ".hidden", "a b"] {
+            let bundle = create_test_bundle(vec![create_test_template(id, None)]);
+            assert!(validate_bundle(&bundle).is_err(), "{} should be rejected", id);
+        }
+        let bundle = create_test_bundle(vec![create_test_template("uv-tool_2.x", None)]);
+        assert!(validate_bundle(&bundle).is_ok());
+    }
+
+    #[test]
+    fn test_unused_path_adds_suffix() {
+        let dir = std::env::temp_dir().join(format!("bundle-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(unused_path(&dir, "bundle", "json"), dir.join("bundle.json"));
+        fs::write(dir.join("bundle.json"), "").unwrap();
+        fs::write(dir.join("bundle-2.json"), "").unwrap();
+        assert_eq!(unused_path(&dir, "bundle", "json"), dir.join("bundle-3.json"));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_preview_reports_conflicts_and_missing_dependencies() {
+        let builtins = vec![create_test_template("node", None)];
+        let custom = vec![create_test_template("mine", None)];
+        let bundle = create_test_bundle(vec![
+            create_test_template("node", None),
+            create_test_template("mine", None),
+            create_test_template("fresh", Some(vec!["ghost"])),
+        ]);
+
+        let preview = preview_import_with_templates(&bundle, &builtins, &custom);
+
+        assert_eq!(preview.conflicts.len(), 2);
+        assert!(preview.conflicts[0].is_builtin);
+        assert!(!preview.conflicts[1].is_builtin);
+        assert_eq!(
+            preview.missing_dependencies,
+            vec![("fresh".to_string(), "ghost".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_plan_import_skip() {
+        let custom = vec![create_test_template("mine", None)];
+        let bundle = create_test_bundle(vec![
+            create_test_template("mine", None),
+            create_test_template("fresh", None),
+        ]);
+
+        let (to_save, result) =
+            plan_import(&bundle, &[], &custom, ConflictResolution::Skip).unwrap();
+
+        assert_eq!(to_save.len(), 1);
+        assert_eq!(result.imported, vec!["fresh"]);
+        assert_eq!(result.skipped, vec!["mine"]);
+    }
+
+    #[test]
+    fn test_plan_import_overwrite_builtin_rejected() {
+        let builtins = vec![create_test_template("node", None)];
+        let bundle = create_test_bundle(vec![create_test_template("node", None)]);
+
+        let result = plan_import(&bundle, &builtins, &[], ConflictResolution::Overwrite);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("built-in"));
+    }
+
+    #[test]
+    fn test_plan_import_rename_rewrites_dependencies() {
+        let custom = vec![
+            create_test_template("base", None),
+            create_test_template("base-imported", None),
+        ];
+        let bundle = create_test_bundle(vec![
+            create_test_template("base", None),
+            create_test_template("tool", Some(vec!["base"])),
+        ]);
+
+        let (to_save, result) =
+            plan_import(&bundle, &[], &custom, ConflictResolution::Rename).unwrap();
+
+        assert_eq!(
+            result.renamed,
+            vec![("base".to_string(), "base-imported-2".to_string())]
+        );
+        let tool = to_save.iter().find(|t| t.id == "tool").unwrap();
+        assert_eq!(tool.dependencies, Some(vec!["base-imported-2".to_string()]));
+    }
+
+    #[test]
+    fn test_plan_import_missing_dependency_rejected() {
+        let bundle = create_test_bundle(vec![create_test_template("tool", Some(vec!["ghost"]))]);
+
+        let result = plan_import(&bundle, &[], &[], ConflictResolution::Skip);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("ghost"));
+    }
+}
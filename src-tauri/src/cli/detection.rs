@@ -5,7 +5,7 @@ use std::env;
 use rayon::prelude::*;
 
 /// Check if an executable exists in PATH
-fn which(executable: &str) -> Option<String> {
+pub fn which(executable: &str) -> Option<String> {
     if let Ok(output) = Command::new("which").arg(executable).output() {
         if output.status.success() {
             let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
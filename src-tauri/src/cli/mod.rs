@@ -1,3 +1,4 @@
+pub mod bundle;
 pub mod detection;
 pub mod storage;
 pub mod template;
@@ -1,3 +1,5 @@
+use crate::dependencies::spec;
+use crate::installation::installer::validate_package_name;
 use crate::types::CliToolTemplate;
 use std::fs;
 use std::path::PathBuf;
@@ -21,10 +23,31 @@ fn get_custom_templates_dir() -> Result<PathBuf, String> {
 
 /// Get the file path for a custom template
 fn get_template_path(id: &str) -> Result<PathBuf, String> {
+    validate_template_id(id)?;
     let dir = get_custom_templates_dir()?;
     Ok(dir.join(format!("{}.json", id)))
 }
 
+/// Check that a template ID is safe to use as a file name
+///
+/// IDs may only contain ASCII letters, digits, `.`, `_` and `-`, and can't
+/// start with a dot, so they can't point outside the templates directory.
+pub fn validate_template_id(id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("Template ID cannot be empty".to_string());
+    }
+    if id.starts_with('.') {
+        return Err(format!("Template ID '{}' cannot start with a dot", id));
+    }
+    if !id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')) {
+        return Err(format!(
+            "Template ID '{}' may only contain letters, digits, '.', '_' and '-'",
+            id
+        ));
+    }
+    Ok(())
+}
+
 /// Load all custom templates from the config directory
 pub fn load_custom_templates() -> Result<Vec<CliToolTemplate>, String> {
     let dir = get_custom_templates_dir()?;
@@ -71,6 +94,36 @@ pub fn save_custom_template(template: &CliToolTemplate) -> Result<(), String> {
     Ok(())
 }
 
+/// Save several custom templates, either all of them or none
+///
+/// Every template is written to a temporary file first; the files are only
+/// renamed into place once all of them were written.
+pub fn save_custom_templates(templates: &[CliToolTemplate]) -> Result<(), String> {
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let result = templates.iter().try_for_each(|template| {
+        let path = get_template_path(&template.id)?;
+        let temp_path = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(template)
+            .map_err(|e| format!("Failed to serialize template: {}", e))?;
+        fs::write(&temp_path, json)
+            .map_err(|e| format!("Failed to write template file {}: {}", temp_path.display(), e))?;
+        staged.push((temp_path, path));
+        Ok(())
+    });
+    if let Err(e) = result {
+        for (temp_path, _) in &staged {
+            let _ = fs::remove_file(temp_path);
+        }
+        return Err(e);
+    }
+
+    for (temp_path, path) in &staged {
+        fs::rename(temp_path, path)
+            .map_err(|e| format!("Failed to write template file {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
 /// Delete a custom template from the config directory
 pub fn delete_custom_template(id: &str) -> Result<(), String> {
     let path = get_template_path(id)?;
@@ -87,9 +140,7 @@ pub fn delete_custom_template(id: &str) -> Result<(), String> {
 
 /// Validate that a template is well-formed
 pub fn validate_template(template: &CliToolTemplate) -> Result<(), String> {
-    if template.id.is_empty() {
-        return Err("Template ID cannot be empty".to_string());
-    }
+    validate_template_id(&template.id)?;
 
     if template.name.is_empty() {
         return Err("Template name cannot be empty".to_string());
@@ -117,12 +168,22 @@ pub fn validate_template(template: &CliToolTemplate) -> Result<(), String> {
         }
     }
 
+    // Package names end up as arguments of (often root) package manager commands
+    for method in template.install_methods.iter().flatten() {
+        let system_packages = method.system_packages.iter().flat_map(|packages| packages.values());
+        let packages = method.system_package.iter().chain(system_packages).chain(&method.package_name);
+        for package in packages {
+            validate_package_name(package)?;
+        }
+    }
+
     // Validate dependencies reference valid tool IDs
     if let Some(deps) = &template.dependencies {
         for dep in deps {
             if dep.is_empty() {
                 return Err("Dependency ID cannot be empty".to_string());
             }
+            spec::parse_dependency(dep)?;
         }
     }
 
@@ -1,4 +1,5 @@
 use crate::types::{CliToolTemplate, InstallMethod, ConfigFileLocation};
+use std::collections::HashMap;
 
 /// Built-in CLI tool templates
 pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
@@ -24,6 +25,63 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "system".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: Some("nodejs".to_string()),
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "runtime-manager".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: Some("node".to_string()),
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: None,
@@ -51,6 +109,65 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "system".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: Some("python3".to_string()),
+                    system_packages: Some(HashMap::from([
+                        ("pacman".to_string(), "python".to_string()),
+                    ])),
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "runtime-manager".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: Some("python".to_string()),
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: None,
@@ -78,6 +195,41 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "runtime-manager".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: Some("uv".to_string()),
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["python".to_string()]),
@@ -100,6 +252,19 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
@@ -134,6 +299,19 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                         "/bin/bash brew-install/install.sh".to_string(),
                         "rm -rf brew-install".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: None,
@@ -161,6 +339,19 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["brew".to_string()]),
@@ -188,9 +379,44 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "system".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: Some("fish".to_string()),
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
-            dependencies: Some(vec!["brew".to_string()]),
+            dependencies: Some(vec!["?brew".to_string()]),
             category: Some("shell".to_string()),
             emoji: Some("🐠".to_string()),
         },
@@ -215,6 +441,19 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["brew".to_string()]),
@@ -242,9 +481,44 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "system".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: Some("neovim".to_string()),
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
-            dependencies: Some(vec!["brew".to_string()]),
+            dependencies: Some(vec!["?brew".to_string()]),
             category: Some("editor".to_string()),
             emoji: Some("✏️".to_string()),
         },
@@ -269,9 +543,44 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "system".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: Some("tmux".to_string()),
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
-            dependencies: Some(vec!["brew".to_string()]),
+            dependencies: Some(vec!["?brew".to_string()]),
             category: Some("terminal".to_string()),
             emoji: Some("🖥️".to_string()),
         },
@@ -283,6 +592,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("ccusage".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -293,6 +624,21 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "pnpm install -g ccusage@latest".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "pnpm remove -g ccusage".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
@@ -307,6 +653,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("@ccusage/codex".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -317,9 +685,24 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "pnpm install -g @ccusage/codex@latest".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "pnpm remove -g @ccusage/codex".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
-            dependencies: Some(vec!["node".to_string()]),
+            dependencies: Some(vec!["node >= 18".to_string()]),
             category: Some("utility".to_string()),
             emoji: Some("🔧".to_string()),
         },
@@ -331,6 +714,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("@anthropic-ai/claude-code".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -341,6 +746,21 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "npm install -g @anthropic-ai/claude-code".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "npm uninstall -g @anthropic-ai/claude-code".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
@@ -368,6 +788,19 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     dmg_url: None,
                     dmg_install_steps: None,
                     script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["brew".to_string()]),
@@ -392,6 +825,22 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "curl -fsSL https://claude.ai/install.sh | bash".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "rm -f ~/.local/bin/claude".to_string(),
+                        "rm -rf ~/.local/share/claude".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: None,
@@ -406,6 +855,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("@google/gemini-cli".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -416,6 +887,21 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "npm install -g @google/gemini-cli".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "npm uninstall -g @google/gemini-cli".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
@@ -430,6 +916,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("@fission-ai/openspec".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -440,6 +948,21 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "npm install -g @fission-ai/openspec@latest".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "npm uninstall -g @fission-ai/openspec".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
@@ -454,6 +977,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("@microsoft/inshellisense".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -464,6 +1009,21 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "npm install -g @microsoft/inshellisense".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "npm uninstall -g @microsoft/inshellisense".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
@@ -478,6 +1038,28 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
             version_parser: "stdout".to_string(),
             config_files: vec![],
             install_methods: Some(vec![
+                InstallMethod {
+                    method_type: "npm".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("tree-sitter-cli".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
                 InstallMethod {
                     method_type: "script".to_string(),
                     brew_cask_name: None,
@@ -488,6 +1070,43 @@ pub fn get_builtin_templates() -> Vec<CliToolTemplate> {
                     script_commands: Some(vec![
                         "npm install -g tree-sitter-cli".to_string(),
                     ]),
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: None,
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: Some(vec![
+                        "npm uninstall -g tree-sitter-cli".to_string(),
+                    ]),
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
+                },
+                InstallMethod {
+                    method_type: "cargo".to_string(),
+                    brew_cask_name: None,
+                    brew_formula_name: None,
+                    brew_tap: None,
+                    dmg_url: None,
+                    dmg_install_steps: None,
+                    script_commands: None,
+                    github_repo: None,
+                    github_asset_pattern: None,
+                    system_package: None,
+                    system_packages: None,
+                    package_name: Some("tree-sitter-cli".to_string()),
+                    package_version: None,
+                    runtime_name: None,
+                    runtime_manager: None,
+                    uninstall_commands: None,
+                    sha256: None,
+                    checksum_url: None,
+                    checksum_pattern: None,
+                    binary_path: None,
                 },
             ]),
             dependencies: Some(vec!["node".to_string()]),
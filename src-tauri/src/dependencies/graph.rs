@@ -0,0 +1,289 @@
+use crate::cli::template;
+use crate::dependencies::resolution::{self, InstalledVersions};
+use crate::dependencies::spec;
+use crate::types::{CliToolTemplate, DependencyGraph, GraphEdge, GraphFormat, GraphNode};
+use std::collections::HashSet;
+
+/// Render the dependency graph of the template catalog
+///
+/// # Arguments
+/// * `format` - Output format
+/// * `scope` - Tools whose dependencies to include, or `None` for every template
+/// * `installed_tools` - Detected tools, used to annotate the nodes
+pub fn export_dependency_graph(
+    format: GraphFormat,
+    scope: Option<&[String]>,
+    installed_tools: &InstalledVersions,
+) -> Result<String, String> {
+    let templates = template::list_all_templates();
+    let graph = build_graph(&templates, scope, installed_tools)?;
+    render(&graph, format)
+}
+
+/// Build the graph of all templates, or of the tools reachable from `scope`
+///
+/// Nodes keep catalog order. Dependencies on unknown tools are left out.
+pub fn build_graph(
+    templates: &[CliToolTemplate],
+    scope: Option<&[String]>,
+    installed_tools: &InstalledVersions,
+) -> Result<DependencyGraph, String> {
+    let included: HashSet<String> = match scope {
+        Some(tool_ids) => resolution::dependency_closure_with_templates(tool_ids, templates)?
+            .into_iter()
+            .collect(),
+        None => templates.iter().map(|t| t.id.clone()).collect(),
+    };
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for template in templates.iter().filter(|t| included.contains(&t.id)) {
+        nodes.push(GraphNode {
+            id: template.id.clone(),
+            name: template.name.clone(),
+            category: template.category.clone(),
+            installed: installed_tools.contains_key(&template.id),
+            version: installed_tools.get(&template.id).cloned().flatten(),
+        });
+
+        for dep_spec in spec::dependency_specs(template) {
+            let alternative = dep_spec.alternatives.len() > 1;
+            for dep in dep_spec.alternatives.iter().filter(|a| included.contains(&a.id)) {
+                edges.push(GraphEdge {
+                    from: template.id.clone(),
+                    to: dep.id.clone(),
+                    constraint: (!dep.constraints.is_empty()).then(|| dep.constraint_text()),
+                    optional: dep_spec.optional,
+                    alternative,
+                });
+            }
+        }
+    }
+
+    Ok(DependencyGraph { nodes, edges })
+}
+
+/// Render a graph in the given format
+pub fn render(graph: &DependencyGraph, format: GraphFormat) -> Result<String, String> {
+    match format {
+        GraphFormat::Dot => Ok(render_dot(graph)),
+        GraphFormat::Mermaid => Ok(render_mermaid(graph)),
+        GraphFormat::Json => serde_json::to_string_pretty(graph)
+            .map_err(|e| format!("Failed to serialize graph: {}", e)),
+    }
+}
+
+/// Node label: the tool name, plus the version or "not installed"
+fn node_label(node: &GraphNode) -> (String, String) {
+    let status = match (&node.version, node.installed) {
+        (Some(version), _) => version.clone(),
+        (None, true) => "installed".to_string(),
+        (None, false) => "not installed".to_string(),
+    };
+    (node.name.clone(), status)
+}
+
+/// Optional and alternative dependencies are drawn dashed
+fn edge_is_dashed(edge: &GraphEdge) -> bool {
+    edge.optional || edge.alternative
+}
+
+fn render_dot(graph: &DependencyGraph) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let quote = |s: &str| format!("\"{}\"", escape(s));
+
+    let mut out = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box, style=\"rounded,filled\"];\n\n");
+
+    for node in &graph.nodes {
+        let (name, status) = node_label(node);
+        let fill = if node.installed { "#d4edda" } else { "#f8d7da" };
+        out.push_str(&format!(
+            "    {} [label=\"{}\\n{}\", fillcolor=\"{}\"];\n",
+            quote(&node.id),
+            escape(&name),
+            escape(&status),
+            fill
+        ));
+    }
+
+    if !graph.edges.is_empty() {
+        out.push('\n');
+    }
+    for edge in &graph.edges {
+        let mut attrs = Vec::new();
+        if let Some(constraint) = &edge.constraint {
+            attrs.push(format!("label={}", quote(constraint)));
+        }
+        if edge_is_dashed(edge) {
+            attrs.push("style=dashed".to_string());
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        out.push_str(&format!("    {} -> {}{};\n", quote(&edge.from), quote(&edge.to), attrs));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(graph: &DependencyGraph) -> String {
+    // Mermaid node IDs can't contain most punctuation, so every other
+    // character (including `_` itself) is escaped as `_<hex>_` to keep
+    // distinct tool IDs apart, e.g. `uv-tool` and `uv_tool`. The `t_` prefix
+    // keeps IDs like `end` or `graph` from being read as keywords.
+    let mermaid_id = |id: &str| -> String {
+        let escaped: String = id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_string()
+                } else {
+                    format!("_{:x}_", u32::from(c))
+                }
+            })
+            .collect();
+        format!("t_{}", escaped)
+    };
+    let text = |s: &str| s.replace('"', "#quot;");
+
+    let mut out = String::from("graph LR\n");
+
+    for node in &graph.nodes {
+        let (name, status) = node_label(node);
+        let class = if node.installed { "installed" } else { "missing" };
+        out.push_str(&format!(
+            "    {}[\"{}<br/>{}\"]:::{}\n",
+            mermaid_id(&node.id),
+            text(&name),
+            text(&status),
+            class
+        ));
+    }
+
+    for edge in &graph.edges {
+        let arrow = if edge_is_dashed(edge) { "-.->" } else { "-->" };
+        let label = edge
+            .constraint
+            .as_ref()
+            .map(|c| format!("|\"{}\"|", text(c)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "    {} {}{} {}\n",
+            mermaid_id(&edge.from),
+            arrow,
+            label,
+            mermaid_id(&edge.to)
+        ));
+    }
+
+    out.push_str("    classDef installed fill:#d4edda,stroke:#28a745\n");
+    out.push_str("    classDef missing fill:#f8d7da,stroke:#dc3545\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_template(id: &str, name: &str, dependencies: Option<Vec<&str>>) -> CliToolTemplate {
+        CliToolTemplate {
+            id: id.to_string(),
+            name: name.to_string(),
+            executable: id.to_string(),
+            version_command: "--version".to_string(),
+            version_parser: "stdout".to_string(),
+            config_files: vec![],
+            install_methods: None,
+            dependencies: dependencies.map(|deps| deps.iter().map(|s| s.to_string()).collect()),
+            category: None,
+            emoji: None,
+        }
+    }
+
+    fn catalog() -> Vec<CliToolTemplate> {
+        vec![
+            create_test_template("node", "Node.js", None),
+            create_test_template("codex", "Codex", Some(vec!["node >= 18"])),
+            create_test_template("python", "Python", None),
+            create_test_template("uv", "uv", Some(vec!["?python"])),
+        ]
+    }
+
+    fn installed_node() -> InstalledVersions {
+        let mut installed = InstalledVersions::new();
+        installed.insert("node".to_string(), Some("20.11.1".to_string()));
+        installed
+    }
+
+    #[test]
+    fn test_scope_limits_graph_to_reachable_tools() {
+        let scope = vec!["codex".to_string()];
+
+        let graph = build_graph(&catalog(), Some(&scope), &installed_node()).unwrap();
+
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["node", "codex"]);
+        assert_eq!(graph.nodes[0].version.as_deref(), Some("20.11.1"));
+        assert!(!graph.nodes[1].installed);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].constraint.as_deref(), Some(">= 18"));
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let graph = build_graph(&catalog(), None, &installed_node()).unwrap();
+
+        let dot = render(&graph, GraphFormat::Dot).unwrap();
+
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"node\" [label=\"Node.js\\n20.11.1\", fillcolor=\"#d4edda\"];"));
+        assert!(dot.contains("\"codex\" -> \"node\" [label=\">= 18\"];"));
+        assert!(dot.contains("\"uv\" -> \"python\" [style=dashed];"));
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let graph = build_graph(&catalog(), None, &installed_node()).unwrap();
+
+        let mermaid = render(&graph, GraphFormat::Mermaid).unwrap();
+
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("    t_codex[\"Codex<br/>not installed\"]:::missing\n"));
+        assert!(mermaid.contains("    t_codex -->|\">= 18\"| t_node\n"));
+        assert!(mermaid.contains("    t_uv -.-> t_python\n"));
+    }
+
+    #[test]
+    fn test_mermaid_ids_stay_distinct() {
+        let templates = vec![
+            create_test_template("uv-tool", "uv (dash)", None),
+            create_test_template("uv_tool", "uv (underscore)", Some(vec!["uv-tool"])),
+        ];
+        let graph = build_graph(&templates, None, &InstalledVersions::new()).unwrap();
+
+        let mermaid = render(&graph, GraphFormat::Mermaid).unwrap();
+
+        assert!(mermaid.contains("    t_uv_2d_tool[\"uv (dash)<br/>not installed\"]:::missing\n"));
+        assert!(mermaid.contains("    t_uv_5f_tool[\"uv (underscore)<br/>not installed\"]:::missing\n"));
+        assert!(mermaid.contains("    t_uv_5f_tool --> t_uv_2d_tool\n"));
+    }
+
+    #[test]
+    fn test_mermaid_ids_are_never_keywords() {
+        let templates = vec![
+            create_test_template("end", "End", None),
+            create_test_template("graph", "Graph", Some(vec!["end"])),
+        ];
+        let graph = build_graph(&templates, None, &InstalledVersions::new()).unwrap();
+
+        let mermaid = render(&graph, GraphFormat::Mermaid).unwrap();
+
+        assert!(mermaid.contains("    t_end[\"End<br/>not installed\"]:::missing\n"));
+        assert!(mermaid.contains("    t_graph --> t_end\n"));
+        assert!(!mermaid.lines().any(|line| line.trim_start().starts_with("end")));
+    }
+}
@@ -1 +1,3 @@
+pub mod graph;
 pub mod resolution;
+pub mod spec;
@@ -1,20 +1,206 @@
 use crate::cli::template;
-use crate::types::{DependencyTree, DependencyNode, CliToolTemplate};
-use std::collections::{HashMap, HashSet};
+use crate::dependencies::spec::{self, DependencyAlternative, DependencySpec};
+use crate::types::{
+    AffectedTool, DependencyTree, DependencyNode, CliToolTemplate, DependencyChoice,
+    DependencyCycleMember, InstallationOrder, InstallationPlan, InstallationPlanItem,
+    PlanItemReason, UninstallImpact,
+};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Installed tools by template ID, with the version detection found (if any)
+pub type InstalledVersions = HashMap<String, Option<String>>;
 
 /// Resolve installation order using topological sort
-pub fn resolve_installation_order(tool_ids: &[String]) -> Result<Vec<String>, String> {
+pub fn resolve_installation_order(tool_ids: &[String]) -> Result<InstallationOrder, String> {
     let templates = template::list_all_templates();
-    resolve_installation_order_with_templates(tool_ids, &templates)
+    resolve_installation_order_with_templates(tool_ids, &templates).map(InstallationOrder)
 }
 
 /// Resolve installation order with provided templates (for testing)
+///
+/// See [`InstallationOrder`] for the ordering guarantees.
 pub fn resolve_installation_order_with_templates(
     tool_ids: &[String],
     templates: &[CliToolTemplate],
 ) -> Result<Vec<String>, String> {
+    topological_order(tool_ids, tool_ids, templates)
+}
+
+/// Topologically sort `tool_ids`, breaking ties with [`order_key`]
+///
+/// `requested` is the caller's original request, which may be a subset of
+/// `tool_ids` when dependencies were pulled in.
+fn topological_order(
+    tool_ids: &[String],
+    requested: &[String],
+    templates: &[CliToolTemplate],
+) -> Result<Vec<String>, String> {
+    let (adj, mut in_degree) = build_install_graph(tool_ids, templates)?;
+    let keys = order_keys(&in_degree, requested, templates);
+
+    // Kahn's algorithm, always taking the smallest ready tool next
+    let mut ready: BTreeSet<&OrderKey> = in_degree
+        .iter()
+        .filter(|(_, &deg)| deg == 0)
+        .map(|(id, _)| &keys[id])
+        .collect();
+
+    let mut result = Vec::new();
+
+    while let Some(key) = ready.pop_first() {
+        let id = &key.id;
+        result.push(id.clone());
+
+        if let Some(neighbors) = adj.get(id) {
+            for neighbor in neighbors {
+                if let Some(deg) = in_degree.get_mut(neighbor) {
+                    *deg -= 1;
+                    if *deg == 0 {
+                        ready.insert(&keys[neighbor]);
+                    }
+                }
+            }
+        }
+    }
+
+    if result.len() != in_degree.len() {
+        return Err("Failed to resolve all dependencies".to_string());
+    }
+
+    Ok(result)
+}
+
+/// Resolve installation waves using topological sort
+pub fn resolve_installation_waves(tool_ids: &[String]) -> Result<Vec<Vec<String>>, String> {
+    let templates = template::list_all_templates();
+    resolve_installation_waves_with_templates(tool_ids, &templates)
+}
+
+/// Resolve installation waves with provided templates (for testing)
+///
+/// Every tool in a wave only depends on tools from earlier waves, so the
+/// tools of one wave can be installed concurrently. Tools inside a wave use
+/// the same tie-break as [`InstallationOrder`].
+pub fn resolve_installation_waves_with_templates(
+    tool_ids: &[String],
+    templates: &[CliToolTemplate],
+) -> Result<Vec<Vec<String>>, String> {
+    let (adj, mut in_degree) = build_install_graph(tool_ids, templates)?;
+    let keys = order_keys(&in_degree, tool_ids, templates);
+
+    let mut current: Vec<&OrderKey> = in_degree
+        .iter()
+        .filter(|(_, &deg)| deg == 0)
+        .map(|(id, _)| &keys[id])
+        .collect();
+    current.sort();
+
+    let mut waves = Vec::new();
+    let mut resolved = 0;
+
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for key in &current {
+            if let Some(neighbors) = adj.get(&key.id) {
+                for neighbor in neighbors {
+                    if let Some(deg) = in_degree.get_mut(neighbor) {
+                        *deg -= 1;
+                        if *deg == 0 {
+                            next.push(&keys[neighbor]);
+                        }
+                    }
+                }
+            }
+        }
+        next.sort();
+
+        resolved += current.len();
+        waves.push(current.iter().map(|key| key.id.clone()).collect());
+        current = next;
+    }
+
+    if resolved != in_degree.len() {
+        return Err("Failed to resolve all dependencies".to_string());
+    }
+
+    Ok(waves)
+}
+
+/// Tie-break key for tools whose dependencies are all satisfied
+///
+/// Compares by position in the caller's request (tools that were not
+/// requested come last), then by category (uncategorized last), then by ID.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct OrderKey {
+    request_index: usize,
+    uncategorized: bool,
+    category: String,
+    id: String,
+}
+
+/// Build the tie-break key of every tool in the graph
+fn order_keys(
+    in_degree: &HashMap<String, usize>,
+    requested: &[String],
+    templates: &[CliToolTemplate],
+) -> HashMap<String, OrderKey> {
+    in_degree
+        .keys()
+        .map(|id| {
+            let category = templates
+                .iter()
+                .find(|t| &t.id == id)
+                .and_then(|t| t.category.clone());
+            let key = OrderKey {
+                request_index: requested.iter().position(|r| r == id).unwrap_or(usize::MAX),
+                uncategorized: category.is_none(),
+                category: category.unwrap_or_default(),
+                id: id.clone(),
+            };
+            (id.clone(), key)
+        })
+        .collect()
+}
+
+/// Adjacency list (dependency -> dependents) and in-degree of every tool
+type InstallGraph = (HashMap<String, Vec<String>>, HashMap<String, usize>);
+
+/// Build the dependency graph between the given tools
+///
+/// Only edges between tools in `tool_ids` are included. A dependency cycle
+/// is an error, see [`find_install_cycle`] for the cycle itself.
+fn build_install_graph(
+    tool_ids: &[String],
+    templates: &[CliToolTemplate],
+) -> Result<InstallGraph, String> {
+    let (graph, cycle) = build_install_graph_with_cycle(tool_ids, templates)?;
+    match cycle {
+        Some(members) => Err(format!(
+            "Circular dependency detected: {}",
+            format_cycle(&members)
+        )),
+        None => Ok(graph),
+    }
+}
+
+/// Find a dependency cycle between the given tools
+///
+/// The cycle is ordered like [`DependencyTree::cycle`]: it starts and ends
+/// with the same tool, and every tool depends on the next one.
+pub fn find_install_cycle(
+    tool_ids: &[String],
+    templates: &[CliToolTemplate],
+) -> Result<Option<Vec<DependencyCycleMember>>, String> {
+    build_install_graph_with_cycle(tool_ids, templates).map(|(_, cycle)| cycle)
+}
+
+/// Build the dependency graph and find the first cycle in it, if any
+fn build_install_graph_with_cycle(
+    tool_ids: &[String],
+    templates: &[CliToolTemplate],
+) -> Result<(InstallGraph, Option<Vec<DependencyCycleMember>>), String> {
     let template_map: HashMap<String, _> = templates
-        .into_iter()
+        .iter()
         .map(|t| (t.id.clone(), t))
         .collect();
 
@@ -25,9 +211,10 @@ pub fn resolve_installation_order_with_templates(
         }
     }
 
-    // Build adjacency list and in-degree count
+    // Build adjacency list, in-degree count and the reverse "depends on" edges
     let mut adj: HashMap<String, Vec<String>> = HashMap::new();
     let mut in_degree: HashMap<String, usize> = HashMap::new();
+    let mut depends_on: HashMap<String, Vec<String>> = HashMap::new();
 
     for id in tool_ids {
         adj.entry(id.clone()).or_default();
@@ -36,14 +223,17 @@ pub fn resolve_installation_order_with_templates(
 
     for id in tool_ids {
         if let Some(template) = template_map.get(id) {
-            if let Some(deps) = &template.dependencies {
-                for dep in deps {
+            for dep_spec in spec::parse_dependencies(template)? {
+                // Every alternative (and optional dependency) in the target set
+                // must be installed first
+                for dep in dep_spec.ids() {
                     // Only include dependencies that are in our target set
-                    if tool_ids.contains(dep) {
-                        adj.entry(dep.clone())
+                    if tool_ids.iter().any(|t| t == dep) {
+                        adj.entry(dep.to_string())
                             .or_default()
                             .push(id.clone());
                         *in_degree.entry(id.clone()).or_insert(0) += 1;
+                        depends_on.entry(id.clone()).or_default().push(dep.to_string());
                     }
                 }
             }
@@ -52,77 +242,305 @@ pub fn resolve_installation_order_with_templates(
 
     // Detect circular dependencies
     let mut visited = HashSet::new();
-    let mut rec_stack = HashSet::new();
     for id in tool_ids {
-        if has_cycle(&adj, id, &mut visited, &mut rec_stack) {
-            return Err("Circular dependency detected".to_string());
+        let mut path = Vec::new();
+        if let Some(cycle) = find_cycle(&depends_on, id, &mut visited, &mut path) {
+            let members = cycle_members(&cycle, |id| {
+                template_map.get(id).map(|t| t.name.clone())
+            });
+            return Ok(((adj, in_degree), Some(members)));
         }
     }
 
-    // Topological sort using Kahn's algorithm
-    let mut queue: Vec<String> = in_degree
+    Ok(((adj, in_degree), None))
+}
+
+/// Find a cycle reachable from `node` by following "depends on" edges
+///
+/// `path` holds the tools currently being explored. When an edge leads back
+/// into the path, the cycle is returned as an ordered list of tool IDs that
+/// starts and ends with the same tool, e.g. `["a", "b", "a"]` for "a depends
+/// on b, which depends on a".
+fn find_cycle(
+    depends_on: &HashMap<String, Vec<String>>,
+    node: &str,
+    visited: &mut HashSet<String>,
+    path: &mut Vec<String>,
+) -> Option<Vec<String>> {
+    if let Some(pos) = path.iter().position(|id| id == node) {
+        let mut cycle = path[pos..].to_vec();
+        cycle.push(node.to_string());
+        return Some(cycle);
+    }
+    if visited.contains(node) {
+        return None;
+    }
+
+    visited.insert(node.to_string());
+    path.push(node.to_string());
+
+    if let Some(deps) = depends_on.get(node) {
+        for dep in deps {
+            if let Some(cycle) = find_cycle(depends_on, dep, visited, path) {
+                return Some(cycle);
+            }
+        }
+    }
+
+    path.pop();
+    None
+}
+
+/// Attach display names to the tool IDs of a cycle
+fn cycle_members<F>(cycle: &[String], name_of: F) -> Vec<DependencyCycleMember>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    cycle
         .iter()
-        .filter(|(_, &deg)| deg == 0)
-        .map(|(id, _)| id.clone())
+        .map(|id| DependencyCycleMember {
+            tool_id: id.clone(),
+            name: name_of(id).unwrap_or_else(|| id.clone()),
+        })
+        .collect()
+}
+
+/// Format a cycle for error messages, e.g. "Tool A (a) -> Tool B (b) -> Tool A (a)"
+pub fn format_cycle(cycle: &[DependencyCycleMember]) -> String {
+    cycle
+        .iter()
+        .map(|m| format!("{} ({})", m.name, m.tool_id))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Resolve an installation plan that pulls in missing transitive dependencies
+pub fn resolve_installation_plan(
+    tool_ids: &[String],
+    installed_tools: &InstalledVersions,
+) -> Result<InstallationPlan, String> {
+    let templates = template::list_all_templates();
+    resolve_installation_plan_with_templates(tool_ids, installed_tools, &templates)
+}
+
+/// Resolve an installation plan with provided templates (for testing)
+///
+/// Requested tools are always part of the plan. Dependencies that are already
+/// installed are reported separately and not expanded any further. For
+/// dependencies with alternatives, version constraints or the optional
+/// marker, the plan records which tool was chosen and why.
+pub fn resolve_installation_plan_with_templates(
+    tool_ids: &[String],
+    installed_tools: &InstalledVersions,
+    templates: &[CliToolTemplate],
+) -> Result<InstallationPlan, String> {
+    let template_map: HashMap<&str, &CliToolTemplate> = templates
+        .iter()
+        .map(|t| (t.id.as_str(), t))
         .collect();
 
-    let mut result = Vec::new();
+    for id in tool_ids {
+        if !template_map.contains_key(id.as_str()) {
+            return Err(format!("Unknown tool ID: {}", id));
+        }
+    }
 
-    while let Some(id) = queue.pop() {
-        result.push(id.clone());
+    let mut expanded: Vec<String> = Vec::new();
+    let mut planned: HashSet<String> = HashSet::new();
+    for id in tool_ids {
+        if planned.insert(id.clone()) {
+            expanded.push(id.clone());
+        }
+    }
 
-        if let Some(neighbors) = adj.get(&id) {
-            for neighbor in neighbors {
-                if let Some(deg) = in_degree.get_mut(neighbor) {
-                    *deg -= 1;
-                    if *deg == 0 {
-                        queue.push(neighbor.clone());
+    let mut required_by: HashMap<String, Vec<String>> = HashMap::new();
+    let mut already_installed: Vec<String> = Vec::new();
+    let mut choices = Vec::new();
+    let mut queue: VecDeque<String> = expanded.iter().cloned().collect();
+
+    while let Some(id) = queue.pop_front() {
+        let template = template_map[id.as_str()];
+        for dep_spec in spec::parse_dependencies(template)? {
+            for dep in dep_spec.ids() {
+                if !template_map.contains_key(dep) {
+                    return Err(format!("Unknown dependency '{}' required by '{}'", dep, id));
+                }
+            }
+
+            let choice = choose_alternative(&dep_spec, installed_tools, &planned);
+
+            if !dep_spec.is_simple() {
+                choices.push(DependencyChoice {
+                    tool_id: id.clone(),
+                    dependency: dep_spec.to_string(),
+                    chosen: choice.alternative.map(|a| a.id.clone()),
+                    reason: choice.reason.clone(),
+                    satisfied: choice.action != ChoiceAction::Unsatisfied,
+                });
+            }
+
+            let dep = match choice.alternative {
+                Some(alternative) => alternative.id.clone(),
+                None => continue,
+            };
+
+            let requesters = required_by.entry(dep.clone()).or_default();
+            if !requesters.contains(&id) {
+                requesters.push(id.clone());
+            }
+
+            match choice.action {
+                // Installed dependencies are satisfied, so their own dependencies are too
+                ChoiceAction::Installed | ChoiceAction::Unsatisfied => {
+                    if !planned.contains(&dep) && !already_installed.contains(&dep) {
+                        already_installed.push(dep);
                     }
                 }
+                ChoiceAction::Planned | ChoiceAction::Skipped => {}
+                ChoiceAction::PullIn => {
+                    planned.insert(dep.clone());
+                    expanded.push(dep.clone());
+                    queue.push_back(dep);
+                }
             }
         }
     }
 
-    if result.len() != tool_ids.len() {
-        return Err("Failed to resolve all dependencies".to_string());
+    // A cycle can't be installed; report it instead of an order
+    if let Some(cycle) = find_install_cycle(&expanded, templates)? {
+        return Ok(InstallationPlan {
+            items: Vec::new(),
+            already_installed,
+            choices,
+            cycle: Some(cycle),
+        });
     }
 
-    Ok(result)
+    let order = topological_order(&expanded, tool_ids, templates)?;
+
+    let items = order
+        .into_iter()
+        .map(|id| InstallationPlanItem {
+            name: template_map[id.as_str()].name.clone(),
+            reason: if tool_ids.contains(&id) {
+                PlanItemReason::Requested
+            } else {
+                PlanItemReason::Dependency
+            },
+            required_by: required_by.get(&id).cloned().unwrap_or_default(),
+            tool_id: id,
+        })
+        .collect();
+
+    Ok(InstallationPlan {
+        items,
+        already_installed,
+        choices,
+        cycle: None,
+    })
 }
 
-/// Check if there's a cycle in the dependency graph
-fn has_cycle(
-    adj: &HashMap<String, Vec<String>>,
-    node: &str,
-    visited: &mut HashSet<String>,
-    rec_stack: &mut HashSet<String>,
-) -> bool {
-    if rec_stack.contains(node) {
-        return true;
-    }
-    if visited.contains(node) {
-        return false;
-    }
+/// What the plan does with a dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChoiceAction {
+    /// An installed tool satisfies it
+    Installed,
+    /// A tool that is already part of the plan satisfies it
+    Planned,
+    /// The chosen tool is pulled into the plan
+    PullIn,
+    /// Optional and not installed, nothing to do
+    Skipped,
+    /// Every alternative is installed, but none satisfies the constraints
+    Unsatisfied,
+}
 
-    visited.insert(node.to_string());
-    rec_stack.insert(node.to_string());
+struct Choice<'a> {
+    alternative: Option<&'a DependencyAlternative>,
+    action: ChoiceAction,
+    reason: String,
+}
 
-    if let Some(neighbors) = adj.get(node) {
-        for neighbor in neighbors {
-            if has_cycle(adj, neighbor, visited, rec_stack) {
-                return true;
+/// Pick the alternative of a dependency the plan relies on
+///
+/// Preference: an installed tool satisfying the constraints, then a tool
+/// already in the plan, then the first alternative that isn't installed.
+/// Optional dependencies are never pulled in.
+fn choose_alternative<'a>(
+    dep_spec: &'a DependencySpec,
+    installed_tools: &InstalledVersions,
+    planned: &HashSet<String>,
+) -> Choice<'a> {
+    for alternative in &dep_spec.alternatives {
+        if let Some(version) = installed_tools.get(&alternative.id) {
+            if alternative.satisfied_by(version.as_deref()) {
+                return Choice {
+                    alternative: Some(alternative),
+                    action: ChoiceAction::Installed,
+                    reason: match version {
+                        Some(v) => format!("{} {} is installed", alternative.id, v),
+                        None => format!("{} is installed", alternative.id),
+                    },
+                };
             }
         }
     }
 
-    rec_stack.remove(node);
-    false
+    if let Some(alternative) = dep_spec.alternatives.iter().find(|a| planned.contains(&a.id)) {
+        return Choice {
+            alternative: Some(alternative),
+            action: ChoiceAction::Planned,
+            reason: format!("{} is already part of the plan", alternative.id),
+        };
+    }
+
+    if dep_spec.optional {
+        return Choice {
+            alternative: None,
+            action: ChoiceAction::Skipped,
+            reason: "Optional dependency is not installed".to_string(),
+        };
+    }
+
+    if let Some(alternative) = dep_spec
+        .alternatives
+        .iter()
+        .find(|a| !installed_tools.contains_key(&a.id))
+    {
+        let reason = if dep_spec.alternatives.len() > 1 {
+            format!("No alternative is installed, installing {}", alternative.id)
+        } else {
+            format!("{} is not installed", alternative.id)
+        };
+        return Choice {
+            alternative: Some(alternative),
+            action: ChoiceAction::PullIn,
+            reason,
+        };
+    }
+
+    let alternative = &dep_spec.alternatives[0];
+    let found = installed_tools
+        .get(&alternative.id)
+        .cloned()
+        .flatten()
+        .unwrap_or_else(|| "unknown version".to_string());
+    Choice {
+        alternative: Some(alternative),
+        action: ChoiceAction::Unsatisfied,
+        reason: format!(
+            "Installed {} ({}) does not satisfy {}",
+            alternative.id,
+            found,
+            alternative.constraint_text()
+        ),
+    }
 }
 
 /// Get dependency tree for a tool
-pub fn get_dependency_tree(tool_id: &str, installed_tools: &HashSet<String>) -> Result<DependencyTree, String> {
+pub fn get_dependency_tree(tool_id: &str, installed_tools: &InstalledVersions) -> Result<DependencyTree, String> {
     let templates = template::list_all_templates();
-    get_dependency_tree_with_templates(tool_id, installed_tools, &templates)
+    get_dependency_tree_with_versions(tool_id, installed_tools, &templates)
 }
 
 /// Get dependency tree with provided templates (for testing)
@@ -130,93 +548,201 @@ pub fn get_dependency_tree_with_templates(
     tool_id: &str,
     installed_tools: &HashSet<String>,
     templates: &[CliToolTemplate],
+) -> Result<DependencyTree, String> {
+    let installed: InstalledVersions = installed_tools
+        .iter()
+        .map(|id| (id.clone(), None))
+        .collect();
+    get_dependency_tree_with_versions(tool_id, &installed, templates)
+}
+
+/// Get dependency tree with provided templates and detected versions
+///
+/// For every dependency the tree shows one tool: the first installed
+/// alternative that satisfies the constraints, otherwise the first installed
+/// alternative, otherwise the first one. Version constraints the shown tool
+/// doesn't satisfy are flagged on the node and listed on the tree.
+pub fn get_dependency_tree_with_versions(
+    tool_id: &str,
+    installed_tools: &InstalledVersions,
+    templates: &[CliToolTemplate],
 ) -> Result<DependencyTree, String> {
     let template_map: HashMap<String, _> = templates
-        .into_iter()
+        .iter()
         .map(|t| (t.id.clone(), t))
         .collect();
 
-    let mut total_tools = 0;
-    let mut installed_count = 0;
-
-    let root = build_dependency_tree_with_templates(
-        tool_id,
-        &template_map,
+    let mut builder = TreeBuilder {
+        template_map: &template_map,
         installed_tools,
-        &mut 0,
-        &mut total_tools,
-        &mut installed_count,
-    )?;
+        path: Vec::new(),
+        cycle: None,
+        total_tools: 0,
+        installed_count: 0,
+        unsatisfied: Vec::new(),
+    };
 
-    let missing_count = total_tools - installed_count;
+    let root = builder.build(tool_id, None)?;
+    let missing_count = builder.total_tools - builder.installed_count;
 
     Ok(DependencyTree {
         root,
-        total_tools,
-        installed_count,
+        total_tools: builder.total_tools,
+        installed_count: builder.installed_count,
         missing_count,
+        cycle: builder.cycle,
+        unsatisfied_constraints: builder.unsatisfied,
     })
 }
 
-fn build_dependency_tree_with_templates(
-    tool_id: &str,
-    template_map: &HashMap<String, &CliToolTemplate>,
-    installed_tools: &HashSet<String>,
-    depth: &mut u32,
-    total_tools: &mut usize,
-    installed_count: &mut usize,
-) -> Result<DependencyNode, String> {
-    // Prevent infinite recursion
-    *depth += 1;
-    if *depth > 50 {
-        return Err("Dependency tree too deep - possible circular dependency".to_string());
-    }
-
-    let template = template_map
-        .get(tool_id)
-        .ok_or_else(|| format!("Unknown tool ID: {}", tool_id))?;
-
-    *total_tools += 1;
-    let installed = installed_tools.contains(tool_id);
-    if installed {
-        *installed_count += 1;
-    }
-
-    let mut dependencies = Vec::new();
-    if let Some(deps) = &template.dependencies {
-        for dep_id in deps {
-            // Avoid cycles in tree building
-            if !template_map.contains_key(dep_id) {
-                continue;
-            }
+/// State shared while building a dependency tree
+///
+/// `path` holds the tools from the root down to the current node. A
+/// dependency that is already on the path closes a cycle: it is recorded in
+/// `cycle` (first one found) and not expanded again.
+struct TreeBuilder<'a> {
+    template_map: &'a HashMap<String, &'a CliToolTemplate>,
+    installed_tools: &'a InstalledVersions,
+    path: Vec<String>,
+    cycle: Option<Vec<DependencyCycleMember>>,
+    total_tools: usize,
+    installed_count: usize,
+    unsatisfied: Vec<String>,
+}
 
-            // Simple cycle detection for tree building
-            if dep_id == tool_id {
-                continue; // Skip self-dependencies
+impl TreeBuilder<'_> {
+    fn build(
+        &mut self,
+        tool_id: &str,
+        requirement: Option<(&DependencySpec, &DependencyAlternative)>,
+    ) -> Result<DependencyNode, String> {
+        let template = *self
+            .template_map
+            .get(tool_id)
+            .ok_or_else(|| format!("Unknown tool ID: {}", tool_id))?;
+
+        self.total_tools += 1;
+        let installed = self.installed_tools.contains_key(tool_id);
+        if installed {
+            self.installed_count += 1;
+        }
+        let version = self.installed_tools.get(tool_id).cloned().flatten();
+
+        let constraint_satisfied = requirement
+            .filter(|(_, alternative)| !alternative.constraints.is_empty())
+            .map(|(_, alternative)| installed && alternative.satisfied_by(version.as_deref()));
+
+        if constraint_satisfied == Some(false) {
+            if let (Some(parent), Some((_, alternative))) = (self.path.last(), requirement) {
+                self.unsatisfied.push(format!(
+                    "{} requires {} {} (found {})",
+                    parent,
+                    tool_id,
+                    alternative.constraint_text(),
+                    version.as_deref().unwrap_or(if installed { "unknown version" } else { "not installed" })
+                ));
             }
+        }
 
-            match build_dependency_tree_with_templates(
-                dep_id,
-                template_map,
-                installed_tools,
-                depth,
-                total_tools,
-                installed_count,
-            ) {
-                Ok(node) => dependencies.push(node),
-                Err(_) => continue, // Skip problematic dependencies
+        self.path.push(tool_id.to_string());
+
+        let mut dependencies = Vec::new();
+        for dep_spec in spec::dependency_specs(template) {
+            let alternative = match self.pick_alternative(&dep_spec) {
+                Some(alternative) => alternative,
+                None => continue,
+            };
+            let dep_id = alternative.id.clone();
+
+            if let Some(pos) = self.path.iter().position(|id| *id == dep_id) {
+                if self.cycle.is_none() {
+                    let mut ids = self.path[pos..].to_vec();
+                    ids.push(dep_id);
+                    self.cycle = Some(cycle_members(&ids, |id| {
+                        self.template_map.get(id).map(|t| t.name.clone())
+                    }));
+                }
+                continue;
             }
+
+            dependencies.push(self.build(&dep_id, Some((&dep_spec, alternative)))?);
         }
+
+        self.path.pop();
+
+        let (requirement, optional) = match requirement {
+            Some((dep_spec, _)) if !dep_spec.is_simple() => (Some(dep_spec.to_string()), dep_spec.optional),
+            _ => (None, false),
+        };
+
+        Ok(DependencyNode {
+            tool_id: tool_id.to_string(),
+            name: template.name.clone(),
+            installed,
+            dependencies,
+            version,
+            requirement,
+            optional,
+            constraint_satisfied,
+        })
     }
 
-    *depth -= 1;
+    /// Choose which alternative of a dependency to show in the tree
+    fn pick_alternative<'s>(&self, dep_spec: &'s DependencySpec) -> Option<&'s DependencyAlternative> {
+        let known: Vec<&DependencyAlternative> = dep_spec
+            .alternatives
+            .iter()
+            .filter(|a| self.template_map.contains_key(&a.id))
+            .collect();
+
+        let installed_version = |a: &DependencyAlternative| self.installed_tools.get(&a.id);
+
+        known
+            .iter()
+            .find(|a| installed_version(a).is_some_and(|v| a.satisfied_by(v.as_deref())))
+            .or_else(|| known.iter().find(|a| installed_version(a).is_some()))
+            .or_else(|| known.first())
+            .copied()
+    }
+}
 
-    Ok(DependencyNode {
-        tool_id: tool_id.to_string(),
-        name: template.name.clone(),
-        installed,
-        dependencies,
-    })
+/// Collect the given tools and everything they depend on, directly or not
+///
+/// Every alternative and optional dependency is followed. Tools come out in
+/// breadth-first order starting with `tool_ids`.
+pub fn dependency_closure_with_templates(
+    tool_ids: &[String],
+    templates: &[CliToolTemplate],
+) -> Result<Vec<String>, String> {
+    let template_map: HashMap<&str, &CliToolTemplate> = templates
+        .iter()
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut closure = Vec::new();
+    let mut queue: VecDeque<String> = VecDeque::new();
+    for id in tool_ids {
+        if !template_map.contains_key(id.as_str()) {
+            return Err(format!("Unknown tool ID: {}", id));
+        }
+        if seen.insert(id.clone()) {
+            queue.push_back(id.clone());
+        }
+    }
+
+    while let Some(id) = queue.pop_front() {
+        for dep_spec in spec::dependency_specs(template_map[id.as_str()]) {
+            for dep in dep_spec.ids() {
+                if template_map.contains_key(dep) && seen.insert(dep.to_string()) {
+                    queue.push_back(dep.to_string());
+                }
+            }
+        }
+        closure.push(id);
+    }
+
+    Ok(closure)
 }
 
 /// Get reverse dependencies with names (for display purposes)
@@ -227,19 +753,134 @@ pub fn get_reverse_dependencies_with_names(
     templates
         .iter()
         .filter(|t| {
-            t.dependencies
-                .as_ref()
-                .map(|deps| deps.contains(&tool_id.to_string()))
-                .unwrap_or(false)
+            spec::dependency_specs(t)
+                .iter()
+                .any(|dep_spec| dep_spec.ids().any(|id| id == tool_id))
         })
         .map(|t| (t.id.clone(), t.name.clone()))
         .collect()
 }
 
+/// Analyze what breaks when uninstalling a set of tools
+pub fn analyze_uninstall_impact(
+    tool_ids: &[String],
+    installed_tools: &InstalledVersions,
+) -> Result<UninstallImpact, String> {
+    let templates = template::list_all_templates();
+    analyze_uninstall_impact_with_templates(tool_ids, installed_tools, &templates)
+}
+
+/// Analyze uninstall impact with provided templates (for testing)
+///
+/// An installed tool outside the removal set breaks when one of its required
+/// dependencies has no installed alternative left once the removal set and
+/// every other broken tool are gone. Optional dependencies never break a
+/// tool, and version constraints are not re-checked. The removal order is the
+/// reverse of the installation order, so dependents are removed first.
+pub fn analyze_uninstall_impact_with_templates(
+    tool_ids: &[String],
+    installed_tools: &InstalledVersions,
+    templates: &[CliToolTemplate],
+) -> Result<UninstallImpact, String> {
+    let template_map: HashMap<&str, &CliToolTemplate> = templates
+        .iter()
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+
+    let mut removal_order = topological_order(tool_ids, tool_ids, templates)?;
+    removal_order.reverse();
+
+    let removed: HashSet<&str> = tool_ids.iter().map(|id| id.as_str()).collect();
+    let mut gone: HashSet<String> = tool_ids.iter().cloned().collect();
+    let mut affected: Vec<AffectedTool> = Vec::new();
+
+    // Each round can only break dependents of tools broken in an earlier round
+    loop {
+        let mut newly_broken = Vec::new();
+
+        for template in templates {
+            if gone.contains(&template.id) || !installed_tools.contains_key(&template.id) {
+                continue;
+            }
+
+            let mut depends_on: Vec<String> = Vec::new();
+            for dep_spec in spec::dependency_specs(template) {
+                if dep_spec.optional {
+                    continue;
+                }
+                let still_available = dep_spec
+                    .ids()
+                    .any(|id| installed_tools.contains_key(id) && !gone.contains(id));
+                if still_available {
+                    continue;
+                }
+                for id in dep_spec.ids().filter(|id| gone.contains(*id)) {
+                    if !depends_on.iter().any(|d| d == id) {
+                        depends_on.push(id.to_string());
+                    }
+                }
+            }
+
+            if !depends_on.is_empty() {
+                newly_broken.push(AffectedTool {
+                    tool_id: template.id.clone(),
+                    name: template.name.clone(),
+                    direct: depends_on.iter().any(|id| removed.contains(id.as_str())),
+                    depends_on,
+                });
+            }
+        }
+
+        if newly_broken.is_empty() {
+            break;
+        }
+        gone.extend(newly_broken.iter().map(|t| t.tool_id.clone()));
+        affected.extend(newly_broken);
+    }
+
+    let name_of = |id: &str| {
+        template_map
+            .get(id)
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let warnings = affected
+        .iter()
+        .map(|tool| {
+            let names: Vec<String> = tool.depends_on.iter().map(|id| name_of(id)).collect();
+            if tool.direct {
+                format!("{} depends on {}, which would be removed", tool.name, names.join(", "))
+            } else {
+                format!("{} depends on {}, which would stop working", tool.name, names.join(", "))
+            }
+        })
+        .collect();
+
+    Ok(UninstallImpact {
+        removal_order,
+        affected,
+        warnings,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn create_categorized_template(
+        id: &str,
+        category: Option<&str>,
+        dependencies: Option<Vec<&str>>,
+    ) -> CliToolTemplate {
+        let mut template = create_test_template(id, id, dependencies);
+        template.category = category.map(|c| c.to_string());
+        template
+    }
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
     fn create_test_template(
         id: &str,
         name: &str,
@@ -254,6 +895,8 @@ mod tests {
             config_files: vec![],
             install_methods: None,
             dependencies: dependencies.map(|deps| deps.iter().map(|s| s.to_string()).collect()),
+            category: None,
+            emoji: None,
         }
     }
 
@@ -412,6 +1055,133 @@ mod tests {
         assert_eq!(order, vec!["b", "c"]);
     }
 
+    #[test]
+    fn test_waves_independent_tools_share_wave() {
+        let templates = vec![
+            create_test_template("b", "Tool B", None),
+            create_test_template("a", "Tool A", None),
+        ];
+        let tool_ids = vec!["b".to_string(), "a".to_string()];
+
+        let waves = resolve_installation_waves_with_templates(&tool_ids, &templates).unwrap();
+
+        // Request order decides inside a wave
+        assert_eq!(waves, vec![vec!["b", "a"]]);
+    }
+
+    #[test]
+    fn test_waves_diamond() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("c", "Tool C", Some(vec!["a"])),
+            create_test_template("d", "Tool D", Some(vec!["b", "c"])),
+        ];
+        let tool_ids = vec!["d".to_string(), "c".to_string(), "b".to_string(), "a".to_string()];
+
+        let waves = resolve_installation_waves_with_templates(&tool_ids, &templates).unwrap();
+
+        assert_eq!(waves, vec![vec!["a"], vec!["c", "b"], vec!["d"]]);
+    }
+
+    #[test]
+    fn test_waves_uneven_chains() {
+        // C depends on B which depends on A, D only depends on A
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("c", "Tool C", Some(vec!["b"])),
+            create_test_template("d", "Tool D", Some(vec!["a"])),
+            create_test_template("e", "Tool E", None),
+        ];
+        let tool_ids: Vec<String> = ["a", "b", "c", "d", "e"].iter().map(|s| s.to_string()).collect();
+
+        let waves = resolve_installation_waves_with_templates(&tool_ids, &templates).unwrap();
+
+        assert_eq!(waves, vec![vec!["a", "e"], vec!["b", "d"], vec!["c"]]);
+    }
+
+    #[test]
+    fn test_waves_circular_dependency() {
+        let templates = vec![
+            create_test_template("a", "Tool A", Some(vec!["b"])),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+        ];
+        let tool_ids = vec!["a".to_string(), "b".to_string()];
+
+        let result = resolve_installation_waves_with_templates(&tool_ids, &templates);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Circular dependency"));
+    }
+
+    #[test]
+    fn test_plan_pulls_in_missing_dependency() {
+        let templates = vec![
+            create_test_template("node", "Node.js", None),
+            create_test_template("ccusage", "ccusage", Some(vec!["node"])),
+        ];
+        let tool_ids = vec!["ccusage".to_string()];
+        let installed = InstalledVersions::new();
+
+        let plan = resolve_installation_plan_with_templates(&tool_ids, &installed, &templates).unwrap();
+
+        let ids: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(ids, vec!["node", "ccusage"]);
+        assert_eq!(plan.items[0].reason, PlanItemReason::Dependency);
+        assert_eq!(plan.items[0].required_by, vec!["ccusage"]);
+        assert_eq!(plan.items[1].reason, PlanItemReason::Requested);
+        assert!(plan.already_installed.is_empty());
+    }
+
+    #[test]
+    fn test_plan_skips_installed_dependency() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("c", "Tool C", Some(vec!["b"])),
+        ];
+        let tool_ids = vec!["c".to_string()];
+        let mut installed = InstalledVersions::new();
+        installed.insert("b".to_string(), None);
+
+        let plan = resolve_installation_plan_with_templates(&tool_ids, &installed, &templates).unwrap();
+
+        // B is installed, so A is never reached
+        let ids: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(ids, vec!["c"]);
+        assert_eq!(plan.already_installed, vec!["b"]);
+    }
+
+    #[test]
+    fn test_plan_transitive_chain() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("c", "Tool C", Some(vec!["b"])),
+        ];
+        let tool_ids = vec!["c".to_string()];
+        let installed = InstalledVersions::new();
+
+        let plan = resolve_installation_plan_with_templates(&tool_ids, &installed, &templates).unwrap();
+
+        let ids: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(plan.items[0].required_by, vec!["b"]);
+    }
+
+    #[test]
+    fn test_plan_unknown_dependency() {
+        let templates = vec![create_test_template("a", "Tool A", Some(vec!["ghost"]))];
+        let tool_ids = vec!["a".to_string()];
+        let installed = InstalledVersions::new();
+
+        let result = resolve_installation_plan_with_templates(&tool_ids, &installed, &templates);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("ghost"));
+    }
+
     #[test]
     fn test_dependency_tree_single_tool() {
         let templates = vec![create_test_template("a", "Tool A", None)];
@@ -490,4 +1260,389 @@ mod tests {
         assert_eq!(tree.root.dependencies[0].dependencies[0].tool_id, "b");
         assert_eq!(tree.root.dependencies[0].dependencies[0].dependencies[0].tool_id, "a");
     }
+
+    #[test]
+    fn test_circular_dependency_reports_cycle_path() {
+        let templates = vec![
+            create_test_template("a", "Tool A", Some(vec!["b"])),
+            create_test_template("b", "Tool B", Some(vec!["c"])),
+            create_test_template("c", "Tool C", Some(vec!["a"])),
+        ];
+        let tool_ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let result = resolve_installation_order_with_templates(&tool_ids, &templates);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Circular dependency detected: Tool A (a) -> Tool B (b) -> Tool C (c) -> Tool A (a)"
+        );
+    }
+
+    #[test]
+    fn test_installation_plan_reports_cycle_members() {
+        let templates = vec![
+            create_test_template("a", "Tool A", Some(vec!["b"])),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+        ];
+
+        let plan = resolve_installation_plan_with_templates(&["a".to_string()], &HashMap::new(), &templates).unwrap();
+
+        assert!(plan.items.is_empty());
+        let cycle: Vec<(&str, &str)> = plan
+            .cycle
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|m| (m.tool_id.as_str(), m.name.as_str()))
+            .collect();
+        assert_eq!(cycle, vec![("a", "Tool A"), ("b", "Tool B"), ("a", "Tool A")]);
+        assert_eq!(
+            find_install_cycle(&["a".to_string(), "b".to_string()], &templates).unwrap(),
+            plan.cycle
+        );
+    }
+
+    #[test]
+    fn test_system_package_tools_do_not_pull_in_brew() {
+        // fish, nvim and tmux install through the system package manager on Linux
+        let templates = template::get_builtin_templates();
+        for id in ["fish", "nvim", "tmux"] {
+            let plan = resolve_installation_plan_with_templates(&[id.to_string()], &HashMap::new(), &templates).unwrap();
+
+            let ids: Vec<&str> = plan.items.iter().map(|item| item.tool_id.as_str()).collect();
+            assert_eq!(ids, vec![id]);
+        }
+    }
+
+    #[test]
+    fn test_dependency_tree_reports_cycle() {
+        let templates = vec![
+            create_test_template("a", "Tool A", Some(vec!["b"])),
+            create_test_template("b", "Tool B", Some(vec!["c"])),
+            create_test_template("c", "Tool C", Some(vec!["b"])),
+        ];
+        let installed: HashSet<String> = HashSet::new();
+
+        let tree = get_dependency_tree_with_templates("a", &installed, &templates).unwrap();
+
+        let cycle: Vec<&str> = tree
+            .cycle
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|m| m.tool_id.as_str())
+            .collect();
+        assert_eq!(cycle, vec!["b", "c", "b"]);
+        assert_eq!(tree.cycle.unwrap()[0].name, "Tool B");
+        assert_eq!(tree.total_tools, 3);
+    }
+
+    #[test]
+    fn test_dependency_tree_self_dependency_is_cycle() {
+        let templates = vec![create_test_template("a", "Tool A", Some(vec!["a"]))];
+        let installed: HashSet<String> = HashSet::new();
+
+        let tree = get_dependency_tree_with_templates("a", &installed, &templates).unwrap();
+
+        assert!(tree.root.dependencies.is_empty());
+        assert_eq!(tree.cycle.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_dependency_tree_many_siblings() {
+        // Siblings must not count towards any depth limit
+        let leaf_ids: Vec<String> = (0..60).map(|i| format!("leaf{}", i)).collect();
+        let mut templates: Vec<CliToolTemplate> = leaf_ids
+            .iter()
+            .map(|id| create_test_template(id, id, None))
+            .collect();
+        templates.push(create_test_template(
+            "root",
+            "Root",
+            Some(leaf_ids.iter().map(|s| s.as_str()).collect()),
+        ));
+        let installed: HashSet<String> = HashSet::new();
+
+        let tree = get_dependency_tree_with_templates("root", &installed, &templates).unwrap();
+
+        assert_eq!(tree.root.dependencies.len(), 60);
+        assert_eq!(tree.total_tools, 61);
+        assert!(tree.cycle.is_none());
+    }
+
+    #[test]
+    fn test_order_independent_tools_follow_request_order() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", None),
+            create_test_template("c", "Tool C", None),
+        ];
+
+        let order = resolve_installation_order_with_templates(&ids(&["c", "a", "b"]), &templates).unwrap();
+
+        assert_eq!(order, vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_order_request_order_beats_category() {
+        let templates = vec![
+            create_categorized_template("a", Some("alpha"), None),
+            create_categorized_template("b", Some("zulu"), None),
+        ];
+
+        let order = resolve_installation_order_with_templates(&ids(&["b", "a"]), &templates).unwrap();
+
+        assert_eq!(order, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_order_multiple_dependencies_exact() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", None),
+            create_test_template("c", "Tool C", Some(vec!["a", "b"])),
+        ];
+
+        let order = resolve_installation_order_with_templates(&ids(&["c", "b", "a"]), &templates).unwrap();
+
+        // C is requested first but must wait, so B and A follow request order
+        assert_eq!(order, vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_order_diamond_exact() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("c", "Tool C", Some(vec!["a"])),
+            create_test_template("d", "Tool D", Some(vec!["b", "c"])),
+        ];
+
+        let order = resolve_installation_order_with_templates(&ids(&["d", "c", "b", "a"]), &templates).unwrap();
+
+        assert_eq!(order, vec!["a", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn test_order_ready_tool_requested_earlier_goes_first() {
+        // B becomes ready after A, but was requested before E
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("e", "Tool E", None),
+        ];
+
+        let order = resolve_installation_order_with_templates(&ids(&["a", "b", "e"]), &templates).unwrap();
+
+        assert_eq!(order, vec!["a", "b", "e"]);
+    }
+
+    #[test]
+    fn test_plan_pulled_in_dependencies_ordered_by_category_then_id() {
+        let templates = vec![
+            create_categorized_template("z", Some("runtime"), None),
+            create_categorized_template("y", Some("runtime"), None),
+            create_categorized_template("w", None, None),
+            create_categorized_template("v", Some("editor"), None),
+            create_categorized_template("x", Some("utility"), Some(vec!["z", "w", "y", "v"])),
+        ];
+        let installed = InstalledVersions::new();
+
+        let plan = resolve_installation_plan_with_templates(&ids(&["x"]), &installed, &templates).unwrap();
+
+        let order: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(order, vec!["v", "y", "z", "w", "x"]);
+    }
+
+    #[test]
+    fn test_order_is_stable_across_runs() {
+        let templates = vec![
+            create_categorized_template("node", Some("runtime"), None),
+            create_categorized_template("python", Some("runtime"), None),
+            create_categorized_template("uv", Some("package-manager"), Some(vec!["python"])),
+            create_categorized_template("ccusage", Some("utility"), Some(vec!["node"])),
+            create_categorized_template("gemini", Some("utility"), Some(vec!["node"])),
+            create_categorized_template("tmux", Some("terminal"), None),
+        ];
+        let request = ids(&["gemini", "uv", "tmux", "ccusage", "node", "python"]);
+
+        let first = resolve_installation_order_with_templates(&request, &templates).unwrap();
+        assert_eq!(first, vec!["tmux", "node", "gemini", "ccusage", "python", "uv"]);
+
+        for _ in 0..50 {
+            let order = resolve_installation_order_with_templates(&request, &templates).unwrap();
+            assert_eq!(order, first);
+        }
+    }
+
+    fn versions(list: &[(&str, Option<&str>)]) -> InstalledVersions {
+        list.iter()
+            .map(|(id, v)| (id.to_string(), v.map(|v| v.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_prefers_installed_alternative() {
+        let templates = vec![
+            create_test_template("python", "Python", None),
+            create_test_template("uv", "uv", None),
+            create_test_template("tool", "Tool", Some(vec!["python | uv"])),
+        ];
+        let installed = versions(&[("uv", Some("0.4.0"))]);
+
+        let plan = resolve_installation_plan_with_templates(&ids(&["tool"]), &installed, &templates).unwrap();
+
+        let order: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(order, vec!["tool"]);
+        assert_eq!(plan.already_installed, vec!["uv"]);
+        assert_eq!(plan.choices.len(), 1);
+        assert_eq!(plan.choices[0].dependency, "python | uv");
+        assert_eq!(plan.choices[0].chosen.as_deref(), Some("uv"));
+        assert!(plan.choices[0].satisfied);
+    }
+
+    #[test]
+    fn test_plan_installs_first_alternative_when_none_installed() {
+        let templates = vec![
+            create_test_template("python", "Python", None),
+            create_test_template("uv", "uv", None),
+            create_test_template("tool", "Tool", Some(vec!["python | uv"])),
+        ];
+
+        let plan = resolve_installation_plan_with_templates(&ids(&["tool"]), &InstalledVersions::new(), &templates).unwrap();
+
+        let order: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(order, vec!["python", "tool"]);
+        assert_eq!(plan.choices[0].chosen.as_deref(), Some("python"));
+    }
+
+    #[test]
+    fn test_plan_checks_version_constraints() {
+        let templates = vec![
+            create_test_template("node", "Node.js", None),
+            create_test_template("codex", "Codex", Some(vec!["node >= 18"])),
+        ];
+
+        let ok = versions(&[("node", Some("v20.11.1"))]);
+        let plan = resolve_installation_plan_with_templates(&ids(&["codex"]), &ok, &templates).unwrap();
+        assert!(plan.choices[0].satisfied);
+        assert_eq!(plan.already_installed, vec!["node"]);
+
+        let old = versions(&[("node", Some("16.2.0"))]);
+        let plan = resolve_installation_plan_with_templates(&ids(&["codex"]), &old, &templates).unwrap();
+        assert!(!plan.choices[0].satisfied);
+        assert_eq!(plan.choices[0].reason, "Installed node (16.2.0) does not satisfy >= 18");
+    }
+
+    #[test]
+    fn test_plan_skips_optional_dependency() {
+        let templates = vec![
+            create_test_template("gh", "GitHub CLI", None),
+            create_test_template("tool", "Tool", Some(vec!["?gh"])),
+        ];
+
+        let plan = resolve_installation_plan_with_templates(&ids(&["tool"]), &InstalledVersions::new(), &templates).unwrap();
+        let order: Vec<&str> = plan.items.iter().map(|i| i.tool_id.as_str()).collect();
+        assert_eq!(order, vec!["tool"]);
+        assert_eq!(plan.choices[0].chosen, None);
+
+        // Optional dependencies still come first when both are installed together
+        let order = resolve_installation_order_with_templates(&ids(&["tool", "gh"]), &templates).unwrap();
+        assert_eq!(order, vec!["gh", "tool"]);
+    }
+
+    #[test]
+    fn test_dependency_tree_flags_unsatisfied_constraint() {
+        let templates = vec![
+            create_test_template("node", "Node.js", None),
+            create_test_template("codex", "Codex", Some(vec!["node >= 18"])),
+        ];
+        let installed = versions(&[("node", Some("16.2.0"))]);
+
+        let tree = get_dependency_tree_with_versions("codex", &installed, &templates).unwrap();
+
+        let node = &tree.root.dependencies[0];
+        assert_eq!(node.version.as_deref(), Some("16.2.0"));
+        assert_eq!(node.requirement.as_deref(), Some("node >= 18"));
+        assert_eq!(node.constraint_satisfied, Some(false));
+        assert_eq!(
+            tree.unsatisfied_constraints,
+            vec!["codex requires node >= 18 (found 16.2.0)"]
+        );
+    }
+
+    #[test]
+    fn test_dependency_tree_shows_installed_alternative() {
+        let templates = vec![
+            create_test_template("python", "Python", None),
+            create_test_template("uv", "uv", None),
+            create_test_template("tool", "Tool", Some(vec!["python | uv"])),
+        ];
+        let installed = versions(&[("uv", Some("0.4.0"))]);
+
+        let tree = get_dependency_tree_with_versions("tool", &installed, &templates).unwrap();
+
+        assert_eq!(tree.root.dependencies.len(), 1);
+        assert_eq!(tree.root.dependencies[0].tool_id, "uv");
+        assert!(tree.root.dependencies[0].installed);
+        assert!(tree.unsatisfied_constraints.is_empty());
+    }
+
+    #[test]
+    fn test_uninstall_impact_transitive() {
+        let templates = vec![
+            create_test_template("node", "Node.js", None),
+            create_test_template("pnpm", "pnpm", Some(vec!["node"])),
+            create_test_template("ccusage", "ccusage", Some(vec!["pnpm"])),
+            create_test_template("gemini", "Gemini", Some(vec!["node"])),
+        ];
+        let installed = versions(&[("node", None), ("pnpm", None), ("ccusage", None)]);
+
+        let impact = analyze_uninstall_impact_with_templates(&ids(&["node"]), &installed, &templates).unwrap();
+
+        let affected: Vec<(&str, bool)> = impact
+            .affected
+            .iter()
+            .map(|t| (t.tool_id.as_str(), t.direct))
+            .collect();
+        // Gemini isn't installed, so nothing of it can break
+        assert_eq!(affected, vec![("pnpm", true), ("ccusage", false)]);
+        assert_eq!(
+            impact.warnings,
+            vec![
+                "pnpm depends on Node.js, which would be removed",
+                "ccusage depends on pnpm, which would stop working",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uninstall_impact_installed_alternative_keeps_tool() {
+        let templates = vec![
+            create_test_template("python", "Python", None),
+            create_test_template("uv", "uv", None),
+            create_test_template("tool", "Tool", Some(vec!["python | uv", "?python"])),
+        ];
+        let installed = versions(&[("python", None), ("uv", None), ("tool", None)]);
+
+        let impact = analyze_uninstall_impact_with_templates(&ids(&["python"]), &installed, &templates).unwrap();
+
+        assert!(impact.affected.is_empty());
+        assert!(impact.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_uninstall_removal_order_dependents_first() {
+        let templates = vec![
+            create_test_template("a", "Tool A", None),
+            create_test_template("b", "Tool B", Some(vec!["a"])),
+            create_test_template("c", "Tool C", Some(vec!["b"])),
+        ];
+        let installed = versions(&[("a", None), ("b", None), ("c", None)]);
+
+        let impact = analyze_uninstall_impact_with_templates(&ids(&["a", "c", "b"]), &installed, &templates).unwrap();
+
+        assert_eq!(impact.removal_order, vec!["c", "b", "a"]);
+        assert!(impact.affected.is_empty());
+    }
 }
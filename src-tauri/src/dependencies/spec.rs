@@ -0,0 +1,342 @@
+use crate::types::CliToolTemplate;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Comparison operator of a version constraint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionOp {
+    Ge,
+    Gt,
+    Le,
+    Lt,
+    Eq,
+    Ne,
+}
+
+impl VersionOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            VersionOp::Ge => ">=",
+            VersionOp::Gt => ">",
+            VersionOp::Le => "<=",
+            VersionOp::Lt => "<",
+            VersionOp::Eq => "=",
+            VersionOp::Ne => "!=",
+        }
+    }
+}
+
+/// A single version constraint such as `>= 18`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConstraint {
+    pub op: VersionOp,
+    pub version: String,
+}
+
+impl VersionConstraint {
+    /// Check a detected version against the constraint
+    ///
+    /// `=` and `!=` only compare the components given in the constraint, so
+    /// `= 3.11` matches `3.11.4`.
+    pub fn matches(&self, detected: &str) -> bool {
+        match self.op {
+            VersionOp::Ge => compare_versions(detected, &self.version) != Ordering::Less,
+            VersionOp::Gt => compare_versions(detected, &self.version) == Ordering::Greater,
+            VersionOp::Le => compare_versions(detected, &self.version) != Ordering::Greater,
+            VersionOp::Lt => compare_versions(detected, &self.version) == Ordering::Less,
+            VersionOp::Eq => version_prefix_matches(detected, &self.version),
+            VersionOp::Ne => !version_prefix_matches(detected, &self.version),
+        }
+    }
+}
+
+impl fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.op.as_str(), self.version)
+    }
+}
+
+/// One acceptable tool of a dependency, with optional version constraints
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyAlternative {
+    pub id: String,
+    pub constraints: Vec<VersionConstraint>,
+}
+
+impl DependencyAlternative {
+    /// Check whether a detected version satisfies every constraint
+    ///
+    /// A tool with constraints but no detectable version is not satisfied,
+    /// since the constraint can't be verified.
+    pub fn satisfied_by(&self, version: Option<&str>) -> bool {
+        if self.constraints.is_empty() {
+            return true;
+        }
+        match version {
+            Some(v) => self.constraints.iter().all(|c| c.matches(v)),
+            None => false,
+        }
+    }
+
+    /// Describe the constraints, e.g. ">= 18, < 23"
+    pub fn constraint_text(&self) -> String {
+        self.constraints
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for DependencyAlternative {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.constraints.is_empty() {
+            write!(f, "{}", self.id)
+        } else {
+            write!(f, "{} {}", self.id, self.constraint_text())
+        }
+    }
+}
+
+/// A parsed entry of `CliToolTemplate.dependencies`
+///
+/// Grammar:
+/// ```text
+/// entry       := ["?"] alternative ("|" alternative)*
+/// alternative := id [constraint ("," constraint)*]
+/// constraint  := (">=" | ">" | "<=" | "<" | "=" | "!=") version
+/// ```
+/// Examples: `node`, `node >= 18`, `node >= 18, < 23`, `python | uv`, `?gh`.
+/// A leading `?` marks the dependency as optional: it is never pulled in
+/// automatically, but is ordered before the dependent when both are installed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencySpec {
+    pub alternatives: Vec<DependencyAlternative>,
+    pub optional: bool,
+}
+
+impl DependencySpec {
+    /// All tool IDs mentioned by the dependency
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.alternatives.iter().map(|a| a.id.as_str())
+    }
+
+    /// Whether this is a plain required tool ID without constraints
+    pub fn is_simple(&self) -> bool {
+        !self.optional
+            && self.alternatives.len() == 1
+            && self.alternatives[0].constraints.is_empty()
+    }
+}
+
+impl fmt::Display for DependencySpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.optional {
+            write!(f, "?")?;
+        }
+        let alternatives: Vec<String> = self.alternatives.iter().map(|a| a.to_string()).collect();
+        write!(f, "{}", alternatives.join(" | "))
+    }
+}
+
+/// Parse a single dependency entry
+pub fn parse_dependency(input: &str) -> Result<DependencySpec, String> {
+    let trimmed = input.trim();
+    let (optional, rest) = match trimmed.strip_prefix('?') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+
+    let mut alternatives = Vec::new();
+    for part in rest.split('|') {
+        alternatives.push(parse_alternative(part.trim(), input)?);
+    }
+
+    Ok(DependencySpec {
+        alternatives,
+        optional,
+    })
+}
+
+fn parse_alternative(part: &str, input: &str) -> Result<DependencyAlternative, String> {
+    let id_end = part
+        .find(|c: char| c.is_whitespace() || "<>=!,".contains(c))
+        .unwrap_or(part.len());
+    let id = &part[..id_end];
+    if id.is_empty() {
+        return Err(format!("Dependency '{}' is missing a tool ID", input));
+    }
+
+    let mut constraints = Vec::new();
+    let rest = part[id_end..].trim();
+    if !rest.is_empty() {
+        for constraint in rest.split(',') {
+            constraints.push(parse_constraint(constraint.trim(), input)?);
+        }
+    }
+
+    Ok(DependencyAlternative {
+        id: id.to_string(),
+        constraints,
+    })
+}
+
+fn parse_constraint(text: &str, input: &str) -> Result<VersionConstraint, String> {
+    let (op, version) = [
+        (">=", VersionOp::Ge),
+        ("<=", VersionOp::Le),
+        ("!=", VersionOp::Ne),
+        (">", VersionOp::Gt),
+        ("<", VersionOp::Lt),
+        ("=", VersionOp::Eq),
+    ]
+    .iter()
+    .find_map(|(prefix, op)| text.strip_prefix(prefix).map(|v| (*op, v.trim())))
+    .ok_or_else(|| format!("Invalid version constraint '{}' in dependency '{}'", text, input))?;
+
+    let valid = !version.is_empty()
+        && version
+            .trim_start_matches('v')
+            .split('.')
+            .all(|c| !c.is_empty() && c.chars().all(|ch| ch.is_ascii_digit()));
+    if !valid {
+        return Err(format!("Invalid version '{}' in dependency '{}'", version, input));
+    }
+
+    Ok(VersionConstraint {
+        op,
+        version: version.to_string(),
+    })
+}
+
+/// Parse every dependency of a template
+pub fn parse_dependencies(template: &CliToolTemplate) -> Result<Vec<DependencySpec>, String> {
+    template
+        .dependencies
+        .iter()
+        .flatten()
+        .map(|d| parse_dependency(d))
+        .collect()
+}
+
+/// Parse the dependencies of a template, skipping entries that don't parse
+///
+/// Templates are validated when saved, so this is only lenient towards
+/// hand-edited files.
+pub fn dependency_specs(template: &CliToolTemplate) -> Vec<DependencySpec> {
+    template
+        .dependencies
+        .iter()
+        .flatten()
+        .filter_map(|d| parse_dependency(d).ok())
+        .collect()
+}
+
+/// Numeric components of a version string, e.g. "v18.17.0" -> [18, 17, 0]
+fn version_components(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Compare two dotted versions numerically, padding missing components with 0
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a = version_components(a);
+    let b = version_components(b);
+    let len = a.len().max(b.len());
+
+    for i in 0..len {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        match x.cmp(&y) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn version_prefix_matches(detected: &str, wanted: &str) -> bool {
+    let detected = version_components(detected);
+    let wanted = version_components(wanted);
+    wanted
+        .iter()
+        .enumerate()
+        .all(|(i, w)| detected.get(i).copied().unwrap_or(0) == *w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_id() {
+        let spec = parse_dependency("node").unwrap();
+
+        assert!(spec.is_simple());
+        assert_eq!(spec.ids().collect::<Vec<_>>(), vec!["node"]);
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        let spec = parse_dependency("node >=18, <23").unwrap();
+
+        let alt = &spec.alternatives[0];
+        assert_eq!(alt.id, "node");
+        assert_eq!(alt.constraints.len(), 2);
+        assert_eq!(alt.constraints[0].op, VersionOp::Ge);
+        assert_eq!(alt.constraints[1].op, VersionOp::Lt);
+        assert_eq!(spec.to_string(), "node >= 18, < 23");
+    }
+
+    #[test]
+    fn test_parse_optional_alternatives() {
+        let spec = parse_dependency("? python>=3.10 | uv").unwrap();
+
+        assert!(spec.optional);
+        assert_eq!(spec.ids().collect::<Vec<_>>(), vec!["python", "uv"]);
+        assert_eq!(spec.to_string(), "?python >= 3.10 | uv");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_dependency("").is_err());
+        assert!(parse_dependency("node >=").is_err());
+        assert!(parse_dependency("node ~ 18").is_err());
+        assert!(parse_dependency("python | ").is_err());
+    }
+
+    #[test]
+    fn test_constraint_matching() {
+        let spec = parse_dependency("node >= 18, < 23").unwrap();
+        let alt = &spec.alternatives[0];
+
+        assert!(alt.satisfied_by(Some("18.0.0")));
+        assert!(alt.satisfied_by(Some("v22.11.0")));
+        assert!(!alt.satisfied_by(Some("16.20.2")));
+        assert!(!alt.satisfied_by(Some("23.1")));
+        assert!(!alt.satisfied_by(None));
+    }
+
+    #[test]
+    fn test_equal_matches_prefix() {
+        let spec = parse_dependency("python = 3.11").unwrap();
+        let alt = &spec.alternatives[0];
+
+        assert!(alt.satisfied_by(Some("3.11.4")));
+        assert!(!alt.satisfied_by(Some("3.12.0")));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("2", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("0.4.2", "0.4.10"), Ordering::Less);
+    }
+}
@@ -0,0 +1,185 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Archive formats release assets are extracted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    TarGz,
+    TarXz,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// Detect the archive format from a file name
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_lowercase();
+        if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if lower.ends_with(".tar.xz") || lower.ends_with(".txz") {
+            Some(ArchiveKind::TarXz)
+        } else if lower.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Get the directory archives of installed tools are extracted to
+///
+/// Every tool gets its own subdirectory, so archives that ship runtime files
+/// next to the executable (like Neovim) keep working.
+pub fn get_tools_dir() -> Result<PathBuf, String> {
+    let mut dir = dirs::data_dir()
+        .ok_or("Failed to find data directory")?;
+
+    dir.push("dev-env-helper");
+    dir.push("tools");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create tools directory: {}", e))?;
+    }
+
+    Ok(dir)
+}
+
+/// Extract an archive into `destination`
+///
+/// Entries that would end up outside `destination`, such as `../` paths, are
+/// skipped by the underlying tar and zip readers.
+pub fn extract(archive: &Path, kind: ArchiveKind, destination: &Path) -> Result<(), String> {
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let file = File::open(archive)
+        .map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+    let failed = |e: &dyn std::fmt::Display| format!("Failed to extract {}: {}", archive.display(), e);
+
+    match kind {
+        ArchiveKind::TarGz => tar::Archive::new(flate2::read::GzDecoder::new(file))
+            .unpack(destination)
+            .map_err(|e| failed(&e)),
+        ArchiveKind::TarXz => tar::Archive::new(xz2::read::XzDecoder::new(file))
+            .unpack(destination)
+            .map_err(|e| failed(&e)),
+        ArchiveKind::Zip => zip::ZipArchive::new(file)
+            .and_then(|mut zip| zip.extract(destination))
+            .map_err(|e| failed(&e)),
+    }
+}
+
+/// Collect all files below `dir`, without following symlinked directories
+fn files_below(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => files_below(&path, files),
+            Ok(_) => files.push(path),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Find the executable in an extracted archive
+///
+/// A declared `binary_path` such as `bin/nvim` is matched against the end of
+/// each file's path, so it doesn't need to include a versioned top-level
+/// directory like `nvim-macos-arm64/`. Without one, the file named
+/// `executable` closest to the root is used.
+pub fn find_executable(root: &Path, executable: &str, binary_path: Option<&str>) -> Result<PathBuf, String> {
+    let mut files = Vec::new();
+    files_below(root, &mut files);
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+
+    if let Some(binary_path) = binary_path {
+        let declared = Path::new(binary_path.trim_start_matches("./"));
+        return files
+            .into_iter()
+            .filter(|file| relative(file).ends_with(declared))
+            .min_by_key(|file| relative(file).components().count())
+            .ok_or_else(|| format!("The archive has no file at {}", binary_path));
+    }
+
+    let names = [executable.to_string(), format!("{}.exe", executable)];
+    files
+        .into_iter()
+        .filter(|file| {
+            file.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| names.iter().any(|n| n == name))
+        })
+        .min_by_key(|file| relative(file).components().count())
+        .ok_or_else(|| format!("The archive doesn't contain an executable named '{}'", executable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn tar_with(entries: &[(&str, &[u8])], mut builder: tar::Builder<impl std::io::Write>) {
+        for (path, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *data).unwrap();
+        }
+        builder.into_inner().unwrap();
+    }
+
+    #[test]
+    fn test_detect_archive_kind() {
+        assert_eq!(ArchiveKind::from_file_name("lazygit_0.44.1_Darwin_arm64.tar.gz"), Some(ArchiveKind::TarGz));
+        assert_eq!(ArchiveKind::from_file_name("zig-linux-x86_64.TXZ"), Some(ArchiveKind::TarXz));
+        assert_eq!(ArchiveKind::from_file_name("tree-sitter-macos-arm64.zip"), Some(ArchiveKind::Zip));
+        assert_eq!(ArchiveKind::from_file_name("tree-sitter-linux-x64.gz"), None);
+        assert_eq!(ArchiveKind::from_file_name("jq-linux-amd64"), None);
+    }
+
+    #[test]
+    fn test_extract_and_find_executable() {
+        let dir = scratch_dir("extract");
+        let entries: &[(&str, &[u8])] = &[
+            ("nvim-linux64/bin/nvim", b"binary"),
+            ("nvim-linux64/share/nvim/runtime/nvim", b"not the binary"),
+            ("nvim-linux64/lib/libnvim.so", b"library"),
+        ];
+
+        let tar_gz = dir.join("nvim.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(File::create(&tar_gz).unwrap(), flate2::Compression::default());
+        tar_with(entries, tar::Builder::new(encoder));
+        let tar_xz = dir.join("nvim.tar.xz");
+        tar_with(entries, tar::Builder::new(xz2::write::XzEncoder::new(File::create(&tar_xz).unwrap(), 6)));
+        let zip_path = dir.join("nvim.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        for (path, data) in entries {
+            zip.start_file(*path, zip::write::SimpleFileOptions::default()).unwrap();
+            std::io::Write::write_all(&mut zip, data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        for (archive, kind) in [(tar_gz, ArchiveKind::TarGz), (tar_xz, ArchiveKind::TarXz), (zip_path, ArchiveKind::Zip)] {
+            let out = dir.join(format!("{:?}", kind));
+            extract(&archive, kind, &out).unwrap();
+
+            let found = find_executable(&out, "nvim", None).unwrap();
+            assert_eq!(found, out.join("nvim-linux64/bin/nvim"));
+            assert_eq!(fs::read(&found).unwrap(), b"binary");
+            assert_eq!(find_executable(&out, "x", Some("./lib/libnvim.so")).unwrap(), out.join("nvim-linux64/lib/libnvim.so"));
+            assert!(find_executable(&out, "lazygit", None).is_err());
+            assert!(find_executable(&out, "nvim", Some("bin/vim")).is_err());
+        }
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,5 +1,7 @@
-use std::process::{Command, Stdio};
-use std::thread;
+use crate::installation::installer::{
+    finish, proxy_env_vars, run_streamed, EventSink, InstallEvent, Installer,
+};
+use std::process::Command;
 
 /// Check if Homebrew is installed and available
 pub fn check_brew_available() -> Result<(), String> {
@@ -23,99 +25,141 @@ pub fn check_brew_available() -> Result<(), String> {
 ///
 /// # Arguments
 /// * `cask_name` - The Homebrew cask name to install
-/// * `emit_event` - Callback to emit progress events to the frontend
+/// * `emit` - Sink receiving progress events for the frontend
 ///
 /// # Returns
 /// * `Ok(())` on successful installation
 /// * `Err(String)` with error message on failure
-pub fn install_tool_brew<F>(
-    cask_name: &str,
-    mut emit_event: F,
-) -> Result<(), String>
-where
-    F: FnMut(BrewInstallEvent) + Clone,
-{
-    // Check brew is available first
-    check_brew_available()?;
+pub fn install_tool_brew(cask_name: &str, emit: &EventSink) -> Result<(), String> {
+    BrewInstaller::new(None, cask_name, true).install(emit)
+}
 
-    emit_event(BrewInstallEvent::Status {
-        message: format!("Installing {} via Homebrew...", cask_name),
-    });
+/// Install a tool using Homebrew formula (not cask)
+///
+/// # Arguments
+/// * `formula_name` - The Homebrew formula name to install
+/// * `emit` - Sink receiving progress events for the frontend
+pub fn install_tool_brew_formula(formula_name: &str, emit: &EventSink) -> Result<(), String> {
+    BrewInstaller::new(None, formula_name, false).install(emit)
+}
 
-    // Load proxy settings if enabled
-    let mut env_vars: Vec<(String, String)> = Vec::new();
-    if let Ok(settings) = crate::settings::storage::load_settings() {
-        if let Some(ref proxy) = settings.proxy {
-            if let Some((env_name, env_value)) = crate::settings::proxy::get_proxy_env_var(proxy) {
-                env_vars.push((env_name, env_value));
-            }
+/// Install a tool using Homebrew tap and cask/formula
+///
+/// # Arguments
+/// * `tap` - The Homebrew tap (e.g., "nikitabobko/tap")
+/// * `package_name` - The package name to install
+/// * `is_cask` - Whether this is a cask or formula
+/// * `emit` - Sink receiving progress events for the frontend
+pub fn install_tool_brew_tap(
+    tap: &str,
+    package_name: &str,
+    is_cask: bool,
+    emit: &EventSink,
+) -> Result<(), String> {
+    BrewInstaller::new(Some(tap), package_name, is_cask).install(emit)
+}
+
+/// Installs a Homebrew cask or formula, tapping its repository first if needed
+#[derive(Debug, Clone)]
+pub struct BrewInstaller {
+    pub tap: Option<String>,
+    pub package: String,
+    pub is_cask: bool,
+}
+
+impl BrewInstaller {
+    pub fn new(tap: Option<&str>, package: &str, is_cask: bool) -> Self {
+        Self {
+            tap: tap.map(|t| t.to_string()),
+            package: package.to_string(),
+            is_cask,
         }
     }
 
-    // Spawn brew install process with proxy environment variables if configured
-    let mut cmd = Command::new("brew");
-    cmd.args(["install", "--cask", cask_name])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Set proxy environment variables
-    for (key, value) in env_vars {
-        cmd.env(key, value);
+    fn package_args<'a>(&'a self, action: &'a str) -> Vec<&'a str> {
+        if self.is_cask {
+            vec![action, "--cask", &self.package]
+        } else {
+            vec![action, &self.package]
+        }
     }
 
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| format!("Failed to spawn brew process: {}", e))?;
+    fn tap_and_install(&self, emit: &EventSink) -> Result<(), String> {
+        if let Some(tap) = &self.tap {
+            emit(InstallEvent::Status {
+                message: format!("Tapping {}...", tap),
+            });
+            self.run(&["tap", tap], emit)
+                .map_err(|e| format!("Failed to tap {}: {}", tap, e))?;
+        }
 
-    // Get stdout handle
-    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-    let _stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+        emit(InstallEvent::Status {
+            message: match &self.tap {
+                Some(tap) => format!("Installing {} from tap {}...", self.package, tap),
+                None => format!("Installing {} via Homebrew...", self.package),
+            },
+        });
+        self.run(&self.package_args("install"), emit)
+    }
 
-    // Spawn thread to read output
-    let _emit_clone = emit_event.clone();
-    let _stdout_thread = thread::spawn(move || {
-        use std::io::{BufRead, BufReader};
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            if let Ok(_line) = line {
-                // We can't emit_clone here because it's moved
-                // In production, use channels for proper communication
-            }
+    /// Run `brew <args>`, streaming stdout lines as output events
+    fn run(&self, args: &[&str], emit: &EventSink) -> Result<(), String> {
+        let mut cmd = Command::new("brew");
+        cmd.args(args).envs(proxy_env_vars());
+        run_streamed(cmd, &format!("brew {}", args[0]), emit)
+    }
+}
+
+impl Installer for BrewInstaller {
+    fn plan(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        if let Some(tap) = &self.tap {
+            steps.push(format!("brew tap {}", tap));
         }
-    });
+        steps.push(format!("brew {}", self.package_args("install").join(" ")));
+        steps
+    }
 
-    // Wait for process to complete
-    let status = child.wait()
-        .map_err(|e| format!("Failed to wait for brew process: {}", e))?;
+    fn environment(&self) -> Vec<(String, String)> {
+        proxy_env_vars()
+    }
 
-    _stdout_thread.join().ok();
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        // Check brew is available first
+        check_brew_available()?;
 
-    if status.success() {
-        emit_event(BrewInstallEvent::Success {
-            message: format!("Successfully installed {}", cask_name),
-        });
-        Ok(())
-    } else {
-        let error_msg = if let Some(code) = status.code() {
-            format!("Installation failed with exit code {}", code)
-        } else {
-            "Installation terminated by signal".to_string()
-        };
-        emit_event(BrewInstallEvent::Error {
-            message: error_msg.clone(),
+        let result = self.tap_and_install(emit);
+        finish(emit, result, format!("Successfully installed {}", self.package))
+    }
+
+    fn verify(&self) -> Result<bool, String> {
+        let kind = if self.is_cask { "--cask" } else { "--formula" };
+        let output = Command::new("brew")
+            .args(["list", kind, &self.package])
+            .output()
+            .map_err(|e| format!("Failed to run brew list: {}", e))?;
+        Ok(output.status.success())
+    }
+
+    fn upgrade(&self, emit: &EventSink) -> Result<(), String> {
+        check_brew_available()?;
+
+        emit(InstallEvent::Status {
+            message: format!("Upgrading {} via Homebrew...", self.package),
         });
-        Err(error_msg)
+        let result = self.run(&self.package_args("upgrade"), emit);
+        finish(emit, result, format!("Successfully upgraded {}", self.package))
     }
-}
 
-/// Events emitted during Homebrew installation
-#[derive(Debug, Clone)]
-pub enum BrewInstallEvent {
-    Status { message: String },
-    #[allow(dead_code)]
-    Output { line: String },
-    Success { message: String },
-    Error { message: String },
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String> {
+        check_brew_available()?;
+
+        emit(InstallEvent::Status {
+            message: format!("Uninstalling {} via Homebrew...", self.package),
+        });
+        let result = self.run(&self.package_args("uninstall"), emit);
+        finish(emit, result, format!("Successfully uninstalled {}", self.package))
+    }
 }
 
 /// Get the list of installed casks
@@ -162,207 +206,3 @@ pub fn get_cask_info(cask_name: &str) -> Result<String, String> {
 
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
-
-/// Install a tool using Homebrew formula (not cask)
-///
-/// # Arguments
-/// * `formula_name` - The Homebrew formula name to install
-/// * `emit_event` - Callback to emit progress events to the frontend
-///
-/// # Returns
-/// * `Ok(())` on successful installation
-/// * `Err(String)` with error message on failure
-pub fn install_tool_brew_formula<F>(
-    formula_name: &str,
-    mut emit_event: F,
-) -> Result<(), String>
-where
-    F: FnMut(BrewInstallEvent) + Clone,
-{
-    // Check brew is available first
-    check_brew_available()?;
-
-    emit_event(BrewInstallEvent::Status {
-        message: format!("Installing {} via Homebrew...", formula_name),
-    });
-
-    // Load proxy settings if enabled
-    let mut env_vars: Vec<(String, String)> = Vec::new();
-    if let Ok(settings) = crate::settings::storage::load_settings() {
-        if let Some(ref proxy) = settings.proxy {
-            if let Some((env_name, env_value)) = crate::settings::proxy::get_proxy_env_var(proxy) {
-                env_vars.push((env_name, env_value));
-            }
-        }
-    }
-
-    // Spawn brew install process with proxy environment variables if configured
-    let mut cmd = Command::new("brew");
-    cmd.args(["install", formula_name])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Set proxy environment variables
-    for (key, value) in env_vars {
-        cmd.env(key, value);
-    }
-
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| format!("Failed to spawn brew process: {}", e))?;
-
-    // Get stdout handle
-    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-    let _stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
-
-    // Spawn thread to read output
-    let _emit_clone = emit_event.clone();
-    let _stdout_thread = thread::spawn(move || {
-        use std::io::{BufRead, BufReader};
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            if let Ok(_line) = line {
-                // We can't emit_clone here because it's moved
-                // In production, use channels for proper communication
-            }
-        }
-    });
-
-    // Wait for process to complete
-    let status = child.wait()
-        .map_err(|e| format!("Failed to wait for brew process: {}", e))?;
-
-    _stdout_thread.join().ok();
-
-    if status.success() {
-        emit_event(BrewInstallEvent::Success {
-            message: format!("Successfully installed {}", formula_name),
-        });
-        Ok(())
-    } else {
-        let error_msg = if let Some(code) = status.code() {
-            format!("Installation failed with exit code {}", code)
-        } else {
-            "Installation terminated by signal".to_string()
-        };
-        emit_event(BrewInstallEvent::Error {
-            message: error_msg.clone(),
-        });
-        Err(error_msg)
-    }
-}
-
-/// Install a tool using Homebrew tap and cask/formula
-///
-/// # Arguments
-/// * `tap` - The Homebrew tap (e.g., "nikitabobko/tap")
-/// * `package_name` - The package name to install
-/// * `is_cask` - Whether this is a cask or formula
-/// * `emit_event` - Callback to emit progress events to the frontend
-///
-/// # Returns
-/// * `Ok(())` on successful installation
-/// * `Err(String)` with error message on failure
-pub fn install_tool_brew_tap<F>(
-    tap: &str,
-    package_name: &str,
-    is_cask: bool,
-    mut emit_event: F,
-) -> Result<(), String>
-where
-    F: FnMut(BrewInstallEvent) + Clone,
-{
-    // Check brew is available first
-    check_brew_available()?;
-
-    emit_event(BrewInstallEvent::Status {
-        message: format!("Tapping {}...", tap),
-    });
-
-    // Load proxy settings if enabled
-    let mut env_vars: Vec<(String, String)> = Vec::new();
-    if let Ok(settings) = crate::settings::storage::load_settings() {
-        if let Some(ref proxy) = settings.proxy {
-            if let Some((env_name, env_value)) = crate::settings::proxy::get_proxy_env_var(proxy) {
-                env_vars.push((env_name, env_value));
-            }
-        }
-    }
-
-    // First, tap the repository
-    let mut tap_cmd = Command::new("brew");
-    tap_cmd.arg("tap").arg(tap);
-
-    for (key, value) in &env_vars {
-        tap_cmd.env(key, value);
-    }
-
-    let tap_status = tap_cmd
-        .output()
-        .map_err(|e| format!("Failed to tap repository: {}", e))?;
-
-    if !tap_status.status.success() {
-        let error_msg = format!("Failed to tap {}: {}", tap, String::from_utf8_lossy(&tap_status.stderr));
-        emit_event(BrewInstallEvent::Error {
-            message: error_msg.clone(),
-        });
-        return Err(error_msg);
-    }
-
-    emit_event(BrewInstallEvent::Status {
-        message: format!("Installing {} from tap {}...", package_name, tap),
-    });
-
-    // Now install the package
-    let mut install_cmd = Command::new("brew");
-    if is_cask {
-        install_cmd.args(["install", "--cask", package_name]);
-    } else {
-        install_cmd.args(["install", package_name]);
-    }
-    install_cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-
-    for (key, value) in env_vars {
-        install_cmd.env(key, value);
-    }
-
-    let mut child = install_cmd
-        .spawn()
-        .map_err(|e| format!("Failed to spawn brew install process: {}", e))?;
-
-    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-    let _stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
-
-    let _emit_clone = emit_event.clone();
-    let _stdout_thread = thread::spawn(move || {
-        use std::io::{BufRead, BufReader};
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            if let Ok(_line) = line {
-                // We can't emit_clone here because it's moved
-            }
-        }
-    });
-
-    let status = child.wait()
-        .map_err(|e| format!("Failed to wait for brew process: {}", e))?;
-
-    _stdout_thread.join().ok();
-
-    if status.success() {
-        emit_event(BrewInstallEvent::Success {
-            message: format!("Successfully installed {} from tap {}", package_name, tap),
-        });
-        Ok(())
-    } else {
-        let error_msg = if let Some(code) = status.code() {
-            format!("Installation failed with exit code {}", code)
-        } else {
-            "Installation terminated by signal".to_string()
-        };
-        emit_event(BrewInstallEvent::Error {
-            message: error_msg.clone(),
-        });
-        Err(error_msg)
-    }
-}
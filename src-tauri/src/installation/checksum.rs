@@ -0,0 +1,320 @@
+use crate::installation::download::http_client;
+use crate::types::{ChecksumStatus, ChecksumVerification, GitHubReleaseAsset};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Names of the checksum files GitHub releases commonly publish
+const CHECKSUM_FILES: &[&str] = &[
+    "checksums.txt",
+    "checksums.sha256",
+    "sha256sums",
+    "sha256sums.txt",
+    "sha256sum.txt",
+];
+
+/// Where the expected SHA-256 of a download comes from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChecksumSource {
+    /// Checksum given directly by the template or software config
+    pub sha256: Option<String>,
+    /// Checksum file that lists the download
+    pub checksum_url: Option<String>,
+    /// Regex for the checksum asset of a GitHub release
+    pub checksum_pattern: Option<String>,
+}
+
+impl ChecksumSource {
+    fn is_empty(&self) -> bool {
+        self.sha256.is_none() && self.checksum_url.is_none()
+    }
+
+    /// Fill in the checksum file a GitHub release publishes for an asset
+    ///
+    /// Sources that already name a checksum are returned unchanged.
+    pub async fn with_github_release(self, download_url: &str) -> Result<Self, String> {
+        if !self.is_empty() {
+            return Ok(self);
+        }
+        let checksum_url = github_checksum_url(download_url, self.checksum_pattern.as_deref()).await?;
+        Ok(Self { checksum_url, ..self })
+    }
+}
+
+/// Find the URL of the checksum file published next to a release asset
+///
+/// `download_url` is a `https://github.com/<owner>/<repo>/releases/download/<tag>/<name>`
+/// URL; only assets of the latest release are looked up.
+async fn github_checksum_url(download_url: &str, pattern: Option<&str>) -> Result<Option<String>, String> {
+    let parts: Vec<&str> = download_url
+        .strip_prefix("https://github.com/")
+        .map(|path| path.split('/').collect())
+        .unwrap_or_default();
+    let [owner, repo, "releases", "download", tag, name] = parts[..] else {
+        return Ok(None);
+    };
+
+    let release = crate::software::github::get_latest_release(owner, repo, None).await?;
+    if release.tag_name != tag {
+        return Ok(None);
+    }
+    Ok(find_checksum_asset(&release.assets, name, pattern)?.map(|asset| asset.browser_download_url.clone()))
+}
+
+/// Pick the checksum file for `asset_name` among the assets of a release
+///
+/// With a `pattern`, the first asset matching it is used. Otherwise a
+/// per-asset file like `<asset>.sha256` is preferred over release-wide
+/// files like `checksums.txt` or `SHA256SUMS`.
+pub fn find_checksum_asset<'a>(
+    assets: &'a [GitHubReleaseAsset],
+    asset_name: &str,
+    pattern: Option<&str>,
+) -> Result<Option<&'a GitHubReleaseAsset>, String> {
+    if let Some(pattern) = pattern {
+        let regex = Regex::new(pattern)
+            .map_err(|e| format!("Invalid checksum pattern regex: {}", e))?;
+        return Ok(assets.iter().find(|asset| regex.is_match(&asset.name)));
+    }
+
+    let per_asset = [format!("{}.sha256", asset_name), format!("{}.sha256sum", asset_name)];
+    let release_wide = |name: &str| {
+        let name = name.to_lowercase();
+        CHECKSUM_FILES.contains(&name.as_str())
+            || name.ends_with("_checksums.txt")
+            || name.ends_with("-checksums.txt")
+            || name.ends_with("_sha256sums.txt")
+    };
+
+    Ok(assets
+        .iter()
+        .find(|asset| per_asset.contains(&asset.name))
+        .or_else(|| assets.iter().find(|asset| release_wide(&asset.name))))
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Find the SHA-256 of `file_name` in a checksum file
+///
+/// Understands `sha256sum` output (`<hash>  <name>`, `<hash> *<name>`), BSD
+/// style lines (`SHA256 (<name>) = <hash>`) and files holding a single bare
+/// hash.
+pub fn parse_checksums(content: &str, file_name: &str) -> Option<String> {
+    let names_file = |name: &str| {
+        let name = name.trim().trim_start_matches('*');
+        name.rsplit('/').next() == Some(file_name)
+    };
+
+    for line in content.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("SHA256 (") {
+            if let Some((name, hash)) = rest.split_once(") = ") {
+                if names_file(name) && is_sha256(hash.trim()) {
+                    return Some(hash.trim().to_lowercase());
+                }
+            }
+            continue;
+        }
+
+        let mut fields = line.splitn(2, char::is_whitespace);
+        if let (Some(hash), Some(name)) = (fields.next(), fields.next()) {
+            if is_sha256(hash) && names_file(name) {
+                return Some(hash.to_lowercase());
+            }
+        }
+    }
+
+    // A `<asset>.sha256` file may only hold the hash
+    let mut tokens = content.split_whitespace();
+    match (tokens.next(), tokens.next()) {
+        (Some(hash), None) if is_sha256(hash) => Some(hash.to_lowercase()),
+        _ => None,
+    }
+}
+
+/// Compute the SHA-256 of a file, as lowercase hex
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Get the directory downloads that failed verification are moved to
+fn get_quarantine_dir() -> Result<PathBuf, String> {
+    let mut dir = dirs::data_dir()
+        .ok_or("Failed to find data directory")?;
+
+    dir.push("dev-env-helper");
+    dir.push("quarantine");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create quarantine directory: {}", e))?;
+    }
+
+    Ok(dir)
+}
+
+/// Move a file out of the way, so it can't be opened or installed by mistake
+fn quarantine_in(dir: &Path, path: &Path) -> Result<PathBuf, String> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let destination = dir.join(format!("{}-{}", chrono::Utc::now().format("%Y%m%d%H%M%S"), name));
+
+    // Renaming fails across file systems, so fall back to copying
+    if fs::rename(path, &destination).is_err() {
+        fs::copy(path, &destination)
+            .and_then(|_| fs::remove_file(path))
+            .map_err(|e| format!("Failed to quarantine {}: {}", path.display(), e))?;
+    }
+    Ok(destination)
+}
+
+/// Resolve the expected checksum of `file_name` and where it came from
+async fn expected_sha256(source: &ChecksumSource, file_name: &str) -> Result<Option<(String, String)>, String> {
+    if let Some(sha256) = &source.sha256 {
+        let sha256 = sha256.trim().to_lowercase();
+        if !is_sha256(&sha256) {
+            return Err(format!("'{}' is not a SHA-256 checksum", sha256));
+        }
+        return Ok(Some((sha256, "configured".to_string())));
+    }
+
+    let Some(url) = &source.checksum_url else {
+        return Ok(None);
+    };
+    let response = http_client()?
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch checksum file {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch checksum file {}: HTTP {}", url, response.status()));
+    }
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read checksum file {}: {}", url, e))?;
+
+    let sha256 = parse_checksums(&content, file_name)
+        .ok_or_else(|| format!("Checksum file {} doesn't list {}", url, file_name))?;
+    Ok(Some((sha256, url.clone())))
+}
+
+/// Check a downloaded file against its expected SHA-256
+///
+/// A file that doesn't match is moved to the quarantine directory; use
+/// [`ensure_verified`] to turn that outcome into an error. Files without a
+/// known checksum are reported as unverified.
+pub async fn verify_download(path: &Path, source: &ChecksumSource) -> Result<ChecksumVerification, String> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let expected = expected_sha256(source, &file_name).await?;
+    verify_in(&get_quarantine_dir()?, path, expected)
+}
+
+fn verify_in(
+    quarantine_dir: &Path,
+    path: &Path,
+    expected: Option<(String, String)>,
+) -> Result<ChecksumVerification, String> {
+    let actual = sha256_file(path)?;
+    let (expected, source) = expected.unzip();
+
+    let (status, quarantined_to) = match &expected {
+        None => (ChecksumStatus::Unverified, None),
+        Some(expected) if *expected == actual => (ChecksumStatus::Verified, None),
+        Some(_) => {
+            let moved = quarantine_in(quarantine_dir, path)?;
+            (ChecksumStatus::Mismatch, Some(moved.to_string_lossy().to_string()))
+        }
+    };
+
+    Ok(ChecksumVerification {
+        status,
+        source,
+        expected,
+        actual,
+        quarantined_to,
+    })
+}
+
+/// Fail when a verification found a mismatching file
+pub fn ensure_verified(verification: &ChecksumVerification) -> Result<(), String> {
+    if verification.status != ChecksumStatus::Mismatch {
+        return Ok(());
+    }
+    Err(format!(
+        "Checksum mismatch: expected {}, got {}. The file was moved to {}",
+        verification.expected.as_deref().unwrap_or_default(),
+        verification.actual,
+        verification.quarantined_to.as_deref().unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    fn asset(name: &str) -> GitHubReleaseAsset {
+        GitHubReleaseAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://github.com/o/r/releases/download/v1/{}", name),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_checksum_formats() {
+        let gnu = format!("{}  ./dist/tool-linux.tar.gz\n{} *tool-macos.zip\n", "0".repeat(64), ABC_SHA256);
+        assert_eq!(parse_checksums(&gnu, "tool-macos.zip").as_deref(), Some(ABC_SHA256));
+        assert_eq!(parse_checksums(&gnu, "tool-linux.tar.gz"), Some("0".repeat(64)));
+        assert_eq!(parse_checksums(&gnu, "tool.dmg"), None);
+
+        let bsd = format!("SHA256 (tool.dmg) = {}\n", ABC_SHA256.to_uppercase());
+        assert_eq!(parse_checksums(&bsd, "tool.dmg").as_deref(), Some(ABC_SHA256));
+        assert_eq!(parse_checksums(&format!("{}\n", ABC_SHA256), "anything").as_deref(), Some(ABC_SHA256));
+    }
+
+    #[test]
+    fn test_find_checksum_asset() {
+        let assets = vec![asset("tool-linux.tar.gz"), asset("tool_1.0_checksums.txt"), asset("tool-linux.tar.gz.sha256")];
+
+        let found = find_checksum_asset(&assets, "tool-linux.tar.gz", None).unwrap();
+        assert_eq!(found.unwrap().name, "tool-linux.tar.gz.sha256");
+        let found = find_checksum_asset(&assets, "tool-macos.zip", None).unwrap();
+        assert_eq!(found.unwrap().name, "tool_1.0_checksums.txt");
+        let found = find_checksum_asset(&assets, "tool-macos.zip", Some(r"^SHA256SUMS$")).unwrap();
+        assert!(found.is_none());
+        assert!(find_checksum_asset(&[asset("tool.zip")], "tool.zip", None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_mismatching_download_is_quarantined() {
+        let dir = std::env::temp_dir().join(format!("checksum-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let quarantine = dir.join("quarantine");
+        fs::create_dir_all(&quarantine).unwrap();
+        let file = dir.join("tool.dmg");
+
+        fs::write(&file, "abc").unwrap();
+        let verified = verify_in(&quarantine, &file, Some((ABC_SHA256.to_string(), "configured".to_string()))).unwrap();
+        assert_eq!(verified.status, ChecksumStatus::Verified);
+        assert!(ensure_verified(&verified).is_ok());
+        assert_eq!(verify_in(&quarantine, &file, None).unwrap().status, ChecksumStatus::Unverified);
+
+        let mismatch = verify_in(&quarantine, &file, Some(("0".repeat(64), "configured".to_string()))).unwrap();
+        assert_eq!(mismatch.status, ChecksumStatus::Mismatch);
+        assert_eq!(mismatch.actual, ABC_SHA256);
+        assert!(!file.exists());
+        assert!(Path::new(mismatch.quarantined_to.as_deref().unwrap()).exists());
+        assert!(ensure_verified(&mismatch).unwrap_err().contains("Checksum mismatch"));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
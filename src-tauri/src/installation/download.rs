@@ -1,28 +1,137 @@
-use std::fs::File;
+use crate::cli::detection;
+use crate::installation::installer::{block_on, finish, EventSink, InstallEvent, Installer};
+use crate::installation::archive::{self, ArchiveKind};
+use crate::installation::checksum::{self, ChecksumSource};
+use crate::installation::jobs;
+use crate::installation::manifest;
+use crate::software::asset_selection::AssetUse;
+use crate::software::linux_app::{self, LinuxPackage};
+use crate::types::ChecksumVerification;
+use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Progress of a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    /// Size of the whole file, 0 when the server doesn't report it
+    pub total: u64,
+    /// Bytes of an earlier partial download the transfer resumed from
+    pub resumed_from: u64,
+}
 
 /// Download progress callback type
 #[allow(dead_code)]
-pub type ProgressCallback = fn(downloaded: u64, total: u64);
+pub type ProgressCallback = fn(progress: DownloadProgress);
+
+/// Attempts made before a download is given up
+const DOWNLOAD_ATTEMPTS: u32 = 4;
+/// Delay before the first retry, doubled after every failed attempt
+const RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Create an HTTP client for downloads, using the proxy from the settings
+pub fn http_client() -> Result<reqwest::Client, String> {
+    let mut client_builder = reqwest::Client::builder()
+        .user_agent("dev-env-helper/1.0");
+
+    // Apply proxy settings if enabled
+    if let Ok(settings) = crate::settings::storage::load_settings() {
+        if let Some(ref proxy) = settings.proxy {
+            if proxy.enabled {
+                client_builder = crate::settings::proxy::configure_client_with_proxy(
+                    client_builder,
+                    proxy,
+                )?;
+            }
+        }
+    }
+
+    client_builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Path a download is written to until it is complete
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    destination.with_file_name(name)
+}
+
+/// Path of the `ETag` of the response a partial download came from
+fn etag_path(partial: &Path) -> PathBuf {
+    let mut name = partial.file_name().unwrap_or_default().to_os_string();
+    name.push(".etag");
+    partial.with_file_name(name)
+}
+
+fn discard_partial(partial: &Path) {
+    let _ = std::fs::remove_file(partial);
+    let _ = std::fs::remove_file(etag_path(partial));
+}
+
+/// Size and `ETag` of a partial download that can be resumed
+///
+/// Without an `ETag` the server can't confirm the file is unchanged, so such
+/// partial files are never resumed.
+fn resumable_partial(partial: &Path) -> Option<(u64, String)> {
+    let size = std::fs::metadata(partial).ok()?.len();
+    let etag = std::fs::read_to_string(etag_path(partial)).ok()?;
+    (size > 0 && !etag.is_empty()).then_some((size, etag))
+}
+
+/// Parse `Content-Range: bytes <start>-<end>/<total>`
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let start = range.split_once('-')?.0.trim().parse().ok()?;
+    Some((start, total.trim().parse().ok()))
+}
+
+/// Why a download attempt failed, and whether trying again may help
+struct AttemptError {
+    message: String,
+    retryable: bool,
+}
+
+impl AttemptError {
+    fn retry(message: String) -> Self {
+        Self { message, retryable: true }
+    }
+
+    fn fail(message: String) -> Self {
+        Self { message, retryable: false }
+    }
+}
 
 /// Download a file from URL with progress tracking
 ///
+/// The body is streamed into `<destination>.part`, which is renamed to
+/// `destination` once the download is complete, so an interrupted download
+/// never leaves a truncated file behind. Servers that don't send a
+/// `Content-Length` are supported; progress then reports a total of 0.
+///
+/// Network and server errors are retried with exponential backoff. A partial
+/// file left by an earlier attempt is resumed with a `Range` request guarded
+/// by `If-Range`, so it is only continued while the server's `ETag` is
+/// unchanged; a stale partial file is discarded and downloaded again.
+///
 /// # Arguments
-/// * `_url` - The URL to download from
+/// * `url` - The URL to download from
 /// * `destination` - Path where the file should be saved
-/// * `_progress` - Optional callback for progress updates
+/// * `progress` - Callback for progress updates
 ///
 /// # Returns
 /// * `Ok(PathBuf)` with the path to downloaded file on success
 /// * `Err(String)` with error message on failure
-pub fn download_file<F>(
-    _url: &str,
+pub async fn download_file<F>(
+    url: &str,
     destination: PathBuf,
-    _progress: F,
+    progress: F,
 ) -> Result<PathBuf, String>
 where
-    F: Fn(u64, u64),
+    F: Fn(DownloadProgress),
 {
     // Create parent directory if it doesn't exist
     if let Some(parent) = destination.parent() {
@@ -32,51 +141,242 @@ where
         }
     }
 
-    // For now, return an error indicating this needs to be implemented
-    // with proper HTTP client support
-    Err("Download functionality requires reqwest dependency".to_string())
+    let client = http_client()?;
+    let partial = partial_path(&destination);
+    let job = jobs::current();
+    let mut delay = RETRY_DELAY;
+
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        if is_cancelled(&job) {
+            return Err(jobs::CANCELLED.to_string());
+        }
+        match download_attempt(&client, url, &partial, &progress, &job).await {
+            Ok(()) => break,
+            Err(e) if e.retryable && attempt < DOWNLOAD_ATTEMPTS => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            Err(e) => {
+                // Keep what was downloaded so a later download can resume it
+                if !e.retryable && e.message != jobs::CANCELLED {
+                    discard_partial(&partial);
+                }
+                return Err(e.message);
+            }
+        }
+    }
+
+    std::fs::rename(&partial, &destination)
+        .map_err(|e| format!("Failed to move download to {}: {}", destination.display(), e))?;
+    let _ = std::fs::remove_file(etag_path(&partial));
+
+    Ok(destination)
+}
+
+/// Whether the job running the download has been cancelled
+fn is_cancelled(job: &Option<Arc<jobs::Job>>) -> bool {
+    job.as_ref().is_some_and(|job| job.is_cancelled())
+}
+
+/// Request the file once, resuming the partial file if possible
+///
+/// Stops between chunks with [`jobs::CANCELLED`] once `job` is cancelled.
+async fn download_attempt<F>(
+    client: &reqwest::Client,
+    url: &str,
+    partial: &Path,
+    progress: &F,
+    job: &Option<Arc<jobs::Job>>,
+) -> Result<(), AttemptError>
+where
+    F: Fn(DownloadProgress),
+{
+    use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, RANGE};
+    use reqwest::StatusCode;
+
+    let resumable = resumable_partial(partial);
+    let mut request = client.get(url);
+    if let Some((offset, etag)) = &resumable {
+        request = request
+            .header(RANGE, format!("bytes={}-", offset))
+            .header(IF_RANGE, etag.as_str());
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AttemptError::retry(format!("Network error: {}", e)))?;
+
+    let status = response.status();
+    if status == StatusCode::RANGE_NOT_SATISFIABLE {
+        discard_partial(partial);
+        return Err(AttemptError::retry(format!("HTTP error: {}", status)));
+    }
+    if !status.is_success() {
+        let message = format!("HTTP error: {}", status);
+        return Err(if status.is_server_error() {
+            AttemptError::retry(message)
+        } else {
+            AttemptError::fail(message)
+        });
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+    // Weak ETags can't be used in `If-Range`
+    let etag = header(ETAG).filter(|etag| !etag.starts_with("W/"));
+    let content_range = header(CONTENT_RANGE).and_then(|value| parse_content_range(&value));
+
+    let resume = match (&resumable, status, content_range) {
+        (Some((offset, expected)), StatusCode::PARTIAL_CONTENT, Some((start, total)))
+            if start == *offset && etag.as_ref().is_none_or(|etag| etag == expected) =>
+        {
+            Some((*offset, total))
+        }
+        (_, StatusCode::PARTIAL_CONTENT, _) => {
+            // Not the range that was asked for, start over
+            discard_partial(partial);
+            return Err(AttemptError::retry("Server sent an unexpected range".to_string()));
+        }
+        _ => None,
+    };
+
+    let (mut file, resumed_from, total) = match resume {
+        Some((offset, total)) => {
+            let total = total.unwrap_or_else(|| offset + response.content_length().unwrap_or(0));
+            let file = OpenOptions::new()
+                .append(true)
+                .open(partial)
+                .map_err(|e| AttemptError::fail(format!("Failed to open file: {}", e)))?;
+            (file, offset, total)
+        }
+        None => {
+            // A full response replaces any stale partial file
+            let file = File::create(partial)
+                .map_err(|e| AttemptError::fail(format!("Failed to create file: {}", e)))?;
+            match &etag {
+                Some(etag) => {
+                    let _ = std::fs::write(etag_path(partial), etag);
+                }
+                None => {
+                    let _ = std::fs::remove_file(etag_path(partial));
+                }
+            }
+            (file, 0, response.content_length().unwrap_or(0))
+        }
+    };
+
+    let mut downloaded = resumed_from;
+    if resumed_from > 0 {
+        progress(DownloadProgress { downloaded, total, resumed_from });
+    }
+
+    let mut stream = response.bytes_stream();
+
+    // Download with progress tracking
+    use futures_util::TryStreamExt;
+    while let Some(chunk_result) = stream
+        .try_next()
+        .await
+        .map_err(|e| AttemptError::retry(format!("Download error: {}", e)))?
+    {
+        if is_cancelled(job) {
+            let _ = file.flush();
+            return Err(AttemptError::fail(jobs::CANCELLED.to_string()));
+        }
+        file.write_all(&chunk_result)
+            .map_err(|e| AttemptError::fail(format!("Write error: {}", e)))?;
+        downloaded += chunk_result.len() as u64;
+        progress(DownloadProgress { downloaded, total, resumed_from });
+    }
+
+    file.flush()
+        .map_err(|e| AttemptError::fail(format!("Write error: {}", e)))
+}
+
+/// Reveal a downloaded file in Finder (macOS)
+fn reveal(_path: &Path) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = std::process::Command::new("open")
+            .arg("-R")  // Reveal in Finder
+            .arg(_path)
+            .spawn();
+    }
+    // File managers can't select a file through xdg-open, so open its folder
+    #[cfg(target_os = "linux")]
+    if let Some(folder) = _path.parent() {
+        let _ = std::process::Command::new("xdg-open").arg(folder).spawn();
+    }
+}
+
+/// Download a file, then check it against its expected checksum
+///
+/// The verification is passed to `verified` before a mismatch is turned into
+/// an error, so callers can report it either way.
+async fn download_verified<F, V>(
+    url: &str,
+    destination: PathBuf,
+    checksum: &ChecksumSource,
+    progress: F,
+    verified: V,
+) -> Result<PathBuf, String>
+where
+    F: Fn(DownloadProgress),
+    V: FnOnce(&ChecksumVerification),
+{
+    let downloaded_path = download_file(url, destination, progress).await?;
+    let verification = checksum::verify_download(&downloaded_path, checksum).await?;
+    verified(&verification);
+    checksum::ensure_verified(&verification)?;
+    Ok(downloaded_path)
 }
 
 /// Download a DMG file and open it with Finder
 ///
 /// This function downloads a DMG installer and opens it,
 /// allowing the user to complete the installation manually.
+/// The DMG is only opened once it matches its checksum.
 ///
 /// # Arguments
-/// * `_url` - The URL of the DMG file to download
-/// * `_progress` - Callback for progress updates
+/// * `url` - The URL of the DMG file to download
+/// * `checksum` - Where the expected checksum of the DMG comes from
+/// * `progress` - Callback for progress updates
+/// * `verified` - Callback receiving the checksum verification
 ///
 /// # Returns
 /// * `Ok(PathBuf)` with path to downloaded DMG
 /// * `Err(String)` on failure
-pub fn download_and_open_dmg<F>(
-    _url: &str,
-    _progress: F,
+pub async fn download_and_open_dmg<F, V>(
+    url: &str,
+    checksum: &ChecksumSource,
+    progress: F,
+    verified: V,
 ) -> Result<PathBuf, String>
 where
-    F: Fn(u64, u64),
+    F: Fn(DownloadProgress),
+    V: FnOnce(&ChecksumVerification),
 {
-    // Use configured download directory or system temp directory
-    let download_dir = get_download_dir().unwrap_or_else(|_| std::env::temp_dir());
-    let filename = _url.split('/').last().unwrap_or("download.dmg");
-    let destination = download_dir.join(filename);
-
-    // Download the file
-    let downloaded_path = download_file(_url, destination.clone(), _progress)?;
-
-    // Open with Finder (macOS)
-    #[cfg(target_os = "macos")]
-    {
-        let _status = std::process::Command::new("open")
-            .arg("-R")  // Reveal in Finder
-            .arg(&downloaded_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open Finder: {}", e))?;
-    }
-
+    let downloaded_path = download_verified(url, dmg_destination(url), checksum, progress, verified).await?;
+    reveal(&downloaded_path);
     Ok(downloaded_path)
 }
 
+/// Where [`download_and_open_dmg`] saves a DMG
+///
+/// This is the configured download directory, or the system temp directory
+/// if there is none.
+pub fn dmg_destination(url: &str) -> PathBuf {
+    let download_dir = get_download_dir().unwrap_or_else(|_| std::env::temp_dir());
+    let filename = url.rsplit('/').next().unwrap_or("download.dmg");
+    download_dir.join(filename)
+}
+
 /// Get the download directory for the platform
 /// Checks settings first, then falls back to system default
 pub fn get_download_dir() -> Result<PathBuf, String> {
@@ -155,89 +455,647 @@ pub fn cleanup_temp_files(_older_than: std::time::Duration) -> Result<usize, Str
 
 /// Download a GitHub release asset with progress tracking
 ///
+/// Unless `checksum` names one, the checksum file the release publishes for
+/// the asset (e.g. `checksums.txt` or `SHA256SUMS`) is used for verification.
+///
 /// # Arguments
 /// * `url` - The URL of the asset to download
+/// * `checksum` - Where the expected checksum of the asset comes from
 /// * `progress` - Callback for progress updates
+/// * `verified` - Callback receiving the checksum verification
 ///
 /// # Returns
 /// * `Ok(PathBuf)` with path to downloaded file
 /// * `Err(String)` on failure
-pub async fn download_github_asset<F>(
+pub async fn download_github_asset<F, V>(
     url: &str,
+    checksum: &ChecksumSource,
     progress: F,
+    verified: V,
 ) -> Result<PathBuf, String>
 where
-    F: Fn(u64, u64),
+    F: Fn(DownloadProgress),
+    V: FnOnce(&ChecksumVerification),
 {
-    let download_dir = get_download_dir()?;
+    let destination = download_github_asset_to(url, &get_download_dir()?, checksum, progress, verified).await?;
+    reveal(&destination);
+    Ok(destination)
+}
 
+/// Download a desktop app from a GitHub release and install it if possible
+///
+/// On Linux, AppImages are placed into `~/Applications` with a desktop entry
+/// and `.deb`/`.rpm` packages are installed with the system package manager,
+/// see [`linux_app::install_downloaded`]. The install runs as a job, so it
+/// can be cancelled and shows up in the install history. Anything else is
+/// downloaded and revealed like [`download_github_asset`].
+pub async fn download_github_app<F, V>(
+    url: &str,
+    app_name: &str,
+    checksum: &ChecksumSource,
+    progress: F,
+    verified: V,
+    emit: &EventSink,
+) -> Result<PathBuf, String>
+where
+    F: Fn(DownloadProgress),
+    V: FnOnce(&ChecksumVerification),
+{
+    let file_name = url.rsplit('/').next().unwrap_or_default();
+    let package = LinuxPackage::from_file_name(file_name).filter(|_| cfg!(target_os = "linux"));
+    let Some(package) = package else {
+        return download_github_asset(url, checksum, progress, verified).await;
+    };
+    // Fail before downloading if the package can't be installed anyway
+    if package != LinuxPackage::AppImage {
+        linux_app::package_escalation()?;
+    }
+
+    let downloaded = download_github_asset_to(url, &get_staging_dir()?, checksum, progress, verified).await?;
+
+    // Package managers block for a long time, so keep them off the async runtime
+    let (emit, app_name) = (emit.clone(), app_name.to_string());
+    tokio::task::spawn_blocking(move || {
+        jobs::run_job(&emit, Some(&app_name), Some(package.as_str()), |_, emit| {
+            let installed = linux_app::install_downloaded(&downloaded, &app_name, emit);
+            let result = installed.as_ref().map(|_| ()).map_err(|e| e.clone());
+            finish(emit, result, format!("Successfully installed {}", app_name))?;
+            installed
+        })
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e))?
+}
+
+/// Download a GitHub release asset into `dir`, verified like [`download_github_asset`]
+async fn download_github_asset_to<F, V>(
+    url: &str,
+    dir: &Path,
+    checksum: &ChecksumSource,
+    progress: F,
+    verified: V,
+) -> Result<PathBuf, String>
+where
+    F: Fn(DownloadProgress),
+    V: FnOnce(&ChecksumVerification),
+{
     // Extract filename from URL
     let filename = url
-        .split('/')
-        .last()
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
         .ok_or("Invalid URL: no filename found")?;
 
-    let destination = download_dir.join(filename);
+    let checksum = checksum.clone().with_github_release(url).await?;
+    download_verified(url, dir.join(filename), &checksum, progress, verified).await
+}
 
-    // Create HTTP client with proxy if configured
-    let mut client_builder = reqwest::Client::builder()
-        .user_agent("dev-env-helper/1.0");
+/// Get the directory assets are downloaded to before they are put in place
+fn get_staging_dir() -> Result<PathBuf, String> {
+    let mut dir = dirs::cache_dir()
+        .ok_or("Failed to find cache directory")?;
 
-    // Apply proxy settings if enabled
-    if let Ok(settings) = crate::settings::storage::load_settings() {
-        if let Some(ref proxy) = settings.proxy {
-            if proxy.enabled {
-                client_builder = crate::settings::proxy::configure_client_with_proxy(
-                    client_builder,
-                    proxy,
-                )?;
-            }
-        }
+    dir.push("dev-env-helper");
+    dir.push("downloads");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create staging directory: {}", e))?;
     }
 
-    let client = client_builder
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    Ok(dir)
+}
 
-    // Start download
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
+/// Downloads a DMG and reveals it, so the user can finish the install
+#[derive(Debug, Clone)]
+pub struct DmgInstaller {
+    pub url: String,
+    pub checksum: ChecksumSource,
+    pub executable: String,
+}
 
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+/// Forward download progress to an install event sink
+fn progress_events(emit: &EventSink) -> impl Fn(DownloadProgress) + Send {
+    let emit = emit.clone();
+    move |p: DownloadProgress| {
+        emit(InstallEvent::Progress {
+            downloaded: p.downloaded,
+            total: p.total,
+            resumed_from: p.resumed_from,
+        });
     }
+}
 
-    let total_size = response
-        .content_length()
-        .ok_or("Unknown content length")?;
+/// Forward a checksum verification to an install event sink
+fn verification_event(emit: &EventSink) -> impl FnOnce(&ChecksumVerification) + Send {
+    let emit = emit.clone();
+    move |verification: &ChecksumVerification| emit(InstallEvent::Verification(verification.clone()))
+}
 
-    // Create file
-    let mut file = File::create(&destination)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+/// Describe how a download will be verified, for install plans
+fn verify_step(checksum: &ChecksumSource, fallback: Option<&str>) -> Option<String> {
+    match (&checksum.sha256, &checksum.checksum_url) {
+        (Some(sha256), _) => Some(format!("Verify SHA-256 {}", sha256)),
+        (None, Some(url)) => Some(format!("Verify SHA-256 against {}", url)),
+        (None, None) => fallback.map(|step| step.to_string()),
+    }
+}
 
-    let mut downloaded: u64 = 0;
-    let mut stream = response.bytes_stream();
+impl Installer for DmgInstaller {
+    fn plan(&self) -> Vec<String> {
+        let destination = dmg_destination(&self.url);
+        let mut plan = vec![format!("Download {} to {}", self.url, destination.display())];
+        plan.extend(verify_step(&self.checksum, None));
+        plan.push(format!("Open {}", destination.display()));
+        plan
+    }
 
-    // Download with progress tracking
-    use futures_util::TryStreamExt;
-    while let Some(chunk_result) = stream.try_next().await.map_err(|e| format!("Download error: {}", e))? {
-        file.write_all(&chunk_result)
-            .map_err(|e| format!("Write error: {}", e))?;
-        downloaded += chunk_result.len() as u64;
-        progress(downloaded, total_size);
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        emit(InstallEvent::Status {
+            message: format!("Downloading {}...", self.url),
+        });
+        let result = block_on(download_and_open_dmg(
+            &self.url,
+            &self.checksum,
+            progress_events(emit),
+            verification_event(emit),
+        ))
+        .and_then(|downloaded| downloaded);
+        finish(emit, result.map(|_| ()), "Downloaded installer, finish the installation in Finder".to_string())
     }
 
-    // Open the downloaded file (macOS)
-    #[cfg(target_os = "macos")]
-    {
-        let _ = std::process::Command::new("open")
-            .arg("-R")
-            .arg(&destination)
-            .spawn();
+    fn verify(&self) -> Result<bool, String> {
+        Ok(detection::which(&self.executable).is_some())
+    }
+
+    fn uninstall(&self, _emit: &EventSink) -> Result<(), String> {
+        Err(format!("'{}' was installed from a DMG and must be removed manually", self.executable))
     }
+}
+
+/// Get the directory binaries placed by the app go to, `~/.local/bin`
+pub fn get_local_bin_dir() -> Result<PathBuf, String> {
+    let mut dir = dirs::home_dir()
+        .ok_or("Failed to find home directory")?;
+
+    dir.push(".local");
+    dir.push("bin");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+
+    Ok(dir)
+}
+
+/// Check whether a release asset is a bare executable rather than an
+/// archive or installer package
+fn is_bare_binary(file_name: &str) -> bool {
+    const PACKAGED: &[&str] = &[
+        ".zip", ".tar", ".tar.gz", ".tgz", ".tar.xz", ".txz", ".tar.bz2", ".gz", ".xz",
+        ".dmg", ".pkg", ".deb", ".rpm", ".msi", ".apk", ".sha256", ".sig", ".asc",
+    ];
+    let lower = file_name.to_lowercase();
+    !PACKAGED.iter().any(|ext| lower.ends_with(ext))
+}
+
+/// Copy a downloaded executable into `~/.local/bin` under its executable name
+fn place_binary(downloaded: &Path, executable: &str) -> Result<PathBuf, String> {
+    let destination = get_local_bin_dir()?.join(executable);
+    // Unlink first, so a running old binary or a link into an archive isn't written through
+    let _ = std::fs::remove_file(&destination);
+    std::fs::copy(downloaded, &destination)
+        .map_err(|e| format!("Failed to copy {} to {}: {}", downloaded.display(), destination.display(), e))?;
+    set_executable(&destination)?;
 
     Ok(destination)
 }
+
+/// Mark a placed file as executable, a no-op outside Unix
+pub(crate) fn set_executable(_path: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(_path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make {} executable: {}", _path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Make an executable from an extracted archive available in `~/.local/bin`
+///
+/// On Unix this is a symlink, so the executable still finds the files it was
+/// shipped with; elsewhere the executable is copied.
+fn link_binary(target: &Path, executable: &str) -> Result<PathBuf, String> {
+    set_executable(target)?;
+
+    #[cfg(unix)]
+    {
+        let link = get_local_bin_dir()?.join(executable);
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(target, &link)
+            .map_err(|e| format!("Failed to link {} to {}: {}", link.display(), target.display(), e))?;
+        Ok(link)
+    }
+
+    #[cfg(not(unix))]
+    {
+        place_binary(target, executable)
+    }
+}
+
+/// Downloads the matching asset of a repository's latest GitHub release
+///
+/// Bare executables are placed into `~/.local/bin`. Archives are extracted
+/// into the tools directory and their executable is linked into
+/// `~/.local/bin`. Placed files are recorded in the install manifest so they
+/// can be uninstalled; other assets stay in the download directory.
+#[derive(Debug, Clone)]
+pub struct GitHubReleaseInstaller {
+    /// Template the files are recorded under in the install manifest
+    pub tool_id: String,
+    /// Repository in `owner/repo` form
+    pub repo: String,
+    pub asset_pattern: Option<String>,
+    /// Checksum of the asset, the release's checksum file when empty
+    pub checksum: ChecksumSource,
+    pub executable: String,
+    /// Path of the executable inside an archive asset, e.g. "bin/nvim"
+    pub binary_path: Option<String>,
+}
+
+impl GitHubReleaseInstaller {
+    fn owner_and_repo(&self) -> Result<(&str, &str), String> {
+        self.repo
+            .split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+            .ok_or_else(|| format!("GitHub repository '{}' must look like owner/repo", self.repo))
+    }
+
+    fn download_latest(&self, emit: &EventSink) -> Result<PathBuf, String> {
+        let (owner, repo) = self.owner_and_repo()?;
+
+        emit(InstallEvent::Status {
+            message: format!("Fetching latest release of {}...", self.repo),
+        });
+        let (release, selection) = block_on(crate::software::github::select_latest_asset(
+            owner,
+            repo,
+            AssetUse::CommandLine,
+            self.asset_pattern.as_deref(),
+        ))??;
+        let (Some(name), Some(url)) = (&selection.selected, &selection.url) else {
+            return Err(selection.explanation);
+        };
+
+        emit(InstallEvent::Status {
+            message: selection.explanation.clone(),
+        });
+        emit(InstallEvent::Status {
+            message: format!("Downloading {} ({})...", name, release.tag_name),
+        });
+        if ArchiveKind::from_file_name(name).is_none() && !is_bare_binary(name) {
+            return block_on(download_github_asset(url, &self.checksum, progress_events(emit), verification_event(emit)))?;
+        }
+        block_on(download_github_asset_to(
+            url,
+            &get_staging_dir()?,
+            &self.checksum,
+            progress_events(emit),
+            verification_event(emit),
+        ))?
+    }
+}
+
+impl GitHubReleaseInstaller {
+    /// Put a downloaded asset in place and record what was placed
+    fn place(&self, downloaded: &Path, emit: &EventSink) -> Result<PathBuf, String> {
+        let file_name = downloaded.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let placed = if let Some(kind) = ArchiveKind::from_file_name(file_name) {
+            self.place_archive(downloaded, kind, emit)?
+        } else if is_bare_binary(file_name) {
+            let placed = place_binary(downloaded, &self.executable)?;
+            manifest::record_files(&self.tool_id, "github", std::slice::from_ref(&placed))?;
+            placed
+        } else {
+            return Ok(downloaded.to_path_buf());
+        };
+
+        // The staged download isn't needed once its contents are in place
+        let _ = std::fs::remove_file(downloaded);
+        emit(InstallEvent::Status {
+            message: format!("Placed {}", placed.display()),
+        });
+        Ok(placed)
+    }
+
+    /// Extract an archive into the tools directory and link its executable
+    fn place_archive(&self, archive: &Path, kind: ArchiveKind, emit: &EventSink) -> Result<PathBuf, String> {
+        let install_dir = archive::get_tools_dir()?.join(&self.tool_id);
+        // Files of an earlier version are replaced, not merged
+        if install_dir.exists() {
+            std::fs::remove_dir_all(&install_dir)
+                .map_err(|e| format!("Failed to remove {}: {}", install_dir.display(), e))?;
+        }
+
+        emit(InstallEvent::Status {
+            message: format!("Extracting to {}...", install_dir.display()),
+        });
+        archive::extract(archive, kind, &install_dir)?;
+        let executable = archive::find_executable(&install_dir, &self.executable, self.binary_path.as_deref())?;
+        let link = link_binary(&executable, &self.executable)?;
+
+        manifest::record_files(&self.tool_id, "github", &[link.clone(), install_dir])?;
+        Ok(link)
+    }
+}
+
+/// Remove files the app placed, reporting each one
+pub fn remove_files(files: &[String], emit: &EventSink) -> Result<(), String> {
+    for file in files {
+        let path = Path::new(file);
+        // Symlinks are removed themselves, never followed
+        let Ok(metadata) = std::fs::symlink_metadata(path) else {
+            continue;
+        };
+        emit(InstallEvent::Status {
+            message: format!("Removing {}", path.display()),
+        });
+        let removed = if metadata.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        removed.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+impl Installer for GitHubReleaseInstaller {
+    fn plan(&self) -> Vec<String> {
+        let asset = match &self.asset_pattern {
+            Some(pattern) => format!("the asset matching '{}'", pattern),
+            None => "the asset that best fits this OS and architecture".to_string(),
+        };
+        let download_dir = get_download_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|e| format!("<{}>", e));
+        let staging_dir = dirs::cache_dir()
+            .map(|dir| dir.join("dev-env-helper").join("downloads").display().to_string())
+            .unwrap_or_else(|| "<cache dir>/dev-env-helper/downloads".to_string());
+        let bin_dir = dirs::home_dir()
+            .map(|home| home.join(".local").join("bin").join(&self.executable).display().to_string())
+            .unwrap_or_else(|| format!("~/.local/bin/{}", self.executable));
+
+        let mut plan = vec![
+            format!("Fetch https://api.github.com/repos/{}/releases/latest", self.repo),
+            format!(
+                "Download {} to {}, or to {} if it is neither an archive nor an executable",
+                asset, staging_dir, download_dir
+            ),
+        ];
+        plan.extend(verify_step(
+            &self.checksum,
+            Some("Verify SHA-256 against the release's checksum file, if it publishes one"),
+        ));
+        plan.push(format!("Copy it to {} if it is a bare executable", bin_dir));
+        let tools_dir = dirs::data_dir()
+            .map(|dir| dir.join("dev-env-helper").join("tools").join(&self.tool_id).display().to_string())
+            .unwrap_or_else(|| format!("<data dir>/dev-env-helper/tools/{}", self.tool_id));
+        let executable = self.binary_path.as_deref().unwrap_or(&self.executable);
+        plan.push(format!(
+            "If it is an archive, extract it to {} and link its {} to {}",
+            tools_dir, executable, bin_dir
+        ));
+        plan
+    }
+
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        let result = self.download_latest(emit).and_then(|path| self.place(&path, emit));
+        let message = match &result {
+            Ok(path) => format!("Installed {}", path.display()),
+            Err(_) => String::new(),
+        };
+        finish(emit, result.map(|_| ()), message)
+    }
+
+    fn verify(&self) -> Result<bool, String> {
+        Ok(detection::which(&self.executable).is_some())
+    }
+
+    /// Downloads the latest release again, replacing the placed binary
+    fn upgrade(&self, emit: &EventSink) -> Result<(), String> {
+        self.install(emit)
+    }
+
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String> {
+        let entry = manifest::get_entry(&self.tool_id)?.ok_or_else(|| {
+            format!("'{}' was not installed by this app and must be removed manually", self.executable)
+        })?;
+
+        let result = remove_files(&entry.files, emit).and_then(|_| manifest::remove_entry(&self.tool_id));
+        finish(emit, result, format!("Successfully uninstalled {}", self.executable))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+
+    /// Serve one canned HTTP response per connection
+    ///
+    /// Returns the base URL and the requests received, so tests can check the
+    /// headers that were sent.
+    fn serve(responses: Vec<Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        std::thread::spawn(move || {
+            for (response, stream) in responses.into_iter().zip(listener.incoming()) {
+                let mut stream = stream.unwrap();
+                let mut request = [0u8; 4096];
+                let len = stream.read(&mut request).unwrap_or(0);
+                received.lock().unwrap().push(String::from_utf8_lossy(&request[..len]).to_lowercase());
+                let _ = stream.write_all(&response);
+            }
+        });
+        (url, requests)
+    }
+
+    fn response(head: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!("{}\r\n\r\n", head).into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("download-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn download(url: &str, destination: &Path) -> (Result<PathBuf, String>, Vec<DownloadProgress>) {
+        let events = Mutex::new(Vec::new());
+        let result = block_on(download_file(url, destination.to_path_buf(), |progress| {
+            events.lock().unwrap().push(progress);
+        }))
+        .unwrap();
+        (result, events.into_inner().unwrap())
+    }
+
+    #[test]
+    fn test_download_without_content_length() {
+        let body = vec![b'x'; 64 * 1024];
+        let (url, _) = serve(vec![
+            response("HTTP/1.1 200 OK\r\nConnection: close", &body),
+            response("HTTP/1.1 404 Not Found\r\nContent-Length: 0", b""),
+        ]);
+        let dir = scratch_dir("no-length");
+        let destination = dir.join("tool.bin");
+
+        let (path, events) = download(&format!("{}/tool.bin", url), &destination);
+
+        assert_eq!(path.unwrap(), destination);
+        assert_eq!(std::fs::read(&destination).unwrap(), body);
+        assert!(events.iter().all(|p| p.total == 0));
+        assert_eq!(events.last().unwrap().downloaded, body.len() as u64);
+        assert!(!partial_path(&destination).exists());
+
+        let missing = dir.join("missing.bin");
+        let (result, _) = download(&format!("{}/missing.bin", url), &missing);
+        assert!(result.unwrap_err().contains("404"));
+        assert!(!missing.exists() && !partial_path(&missing).exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_download_resumes_after_dropped_connection() {
+        let body: Vec<u8> = (0..100u8).collect();
+        let (url, requests) = serve(vec![
+            // The connection drops after 40 of 100 bytes
+            response("HTTP/1.1 200 OK\r\nContent-Length: 100\r\nETag: \"v1\"", &body[..40]),
+            response(
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: 60\r\nContent-Range: bytes 40-99/100\r\nETag: \"v1\"",
+                &body[40..],
+            ),
+        ]);
+        let dir = scratch_dir("resume");
+        let destination = dir.join("tool.dmg");
+
+        let (path, events) = download(&format!("{}/tool.dmg", url), &destination);
+
+        assert_eq!(path.unwrap(), destination);
+        assert_eq!(std::fs::read(&destination).unwrap(), body);
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=40-"));
+        assert!(requests[1].contains("if-range: \"v1\""));
+        assert_eq!(
+            events.last(),
+            Some(&DownloadProgress { downloaded: 100, total: 100, resumed_from: 40 })
+        );
+        assert!(!partial_path(&destination).exists());
+        assert!(!etag_path(&partial_path(&destination)).exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_stale_partial_download_is_discarded() {
+        let body = b"fresh contents".to_vec();
+        // The ETag changed, so the server ignores the range and sends it all
+        let (url, requests) = serve(vec![response(
+            &format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nETag: \"v2\"", body.len()),
+            &body,
+        )]);
+        let dir = scratch_dir("stale");
+        let destination = dir.join("tool.zip");
+        let partial = partial_path(&destination);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&partial, b"old partial").unwrap();
+        std::fs::write(etag_path(&partial), "\"v1\"").unwrap();
+
+        let (path, events) = download(&format!("{}/tool.zip", url), &destination);
+
+        assert_eq!(path.unwrap(), destination);
+        assert_eq!(std::fs::read(&destination).unwrap(), body);
+        assert!(requests.lock().unwrap()[0].contains("if-range: \"v1\""));
+        assert!(events.iter().all(|p| p.resumed_from == 0));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cancelled_download_stops_between_chunks() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/tool.dmg", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request);
+            let _ = stream.write_all(&response("HTTP/1.1 200 OK\r\nContent-Length: 100", &[b'x'; 40]));
+            let _ = stream.flush();
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            let _ = stream.write_all(&[b'y'; 60]);
+        });
+        let dir = scratch_dir("cancel");
+        let destination = dir.join("tool.dmg");
+        let job = jobs::start_job();
+
+        let result = jobs::run_in_job(&job, || {
+            block_on(download_file(&url, destination.clone(), |_| {
+                jobs::cancel_job(&job.id).unwrap();
+            }))
+            .unwrap()
+        });
+        jobs::finish_job(&job.id);
+
+        assert_eq!(result.unwrap_err(), jobs::CANCELLED);
+        assert!(!destination.exists());
+        // The partial file is kept so the download can be resumed later
+        assert_eq!(std::fs::read(partial_path(&destination)).unwrap().len(), 40);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remove_files_removes_links_without_following_them() {
+        let dir = scratch_dir("remove");
+        let tool_dir = dir.join("tools/nvim");
+        std::fs::create_dir_all(tool_dir.join("bin")).unwrap();
+        std::fs::write(tool_dir.join("bin/nvim"), "binary").unwrap();
+        let kept = dir.join("kept");
+        std::fs::create_dir_all(&kept).unwrap();
+        let file_link = dir.join("nvim");
+        let dir_link = dir.join("kept-link");
+        std::os::unix::fs::symlink(tool_dir.join("bin/nvim"), &file_link).unwrap();
+        std::os::unix::fs::symlink(&kept, &dir_link).unwrap();
+
+        let files: Vec<String> = [&file_link, &dir_link, &tool_dir]
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let emit: EventSink = std::sync::Arc::new(|_| {});
+        remove_files(&files, &emit).unwrap();
+
+        assert!(std::fs::symlink_metadata(&file_link).is_err());
+        assert!(std::fs::symlink_metadata(&dir_link).is_err());
+        assert!(!tool_dir.exists());
+        assert!(kept.exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_is_bare_binary() {
+        assert!(is_bare_binary("jq-linux-amd64"));
+        assert!(is_bare_binary("yq_darwin_arm64"));
+        assert!(!is_bare_binary("ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz"));
+        assert!(!is_bare_binary("WezTerm-macos.ZIP"));
+        assert!(!is_bare_binary("lazygit.deb"));
+    }
+}
@@ -0,0 +1,427 @@
+use crate::cli::detection;
+use crate::installation::installer::{
+    command_from, finish, proxy_env_vars, run_streamed, validate_package_name, EventSink, InstallEvent,
+    Installer,
+};
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A language package manager that installs command line tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    Npm,
+    Pipx,
+    UvTool,
+    Cargo,
+    Go,
+}
+
+impl Ecosystem {
+    /// Map an `InstallMethod.method_type` to its ecosystem
+    pub fn from_method_type(method_type: &str) -> Option<Self> {
+        match method_type {
+            "npm" => Some(Ecosystem::Npm),
+            "pipx" => Some(Ecosystem::Pipx),
+            "uv-tool" => Some(Ecosystem::UvTool),
+            "cargo" => Some(Ecosystem::Cargo),
+            "go" => Some(Ecosystem::Go),
+            _ => None,
+        }
+    }
+
+    /// The executable that has to be installed before this ecosystem works
+    pub fn runtime(&self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::Pipx => "pipx",
+            Ecosystem::UvTool => "uv",
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Go => "go",
+        }
+    }
+
+    /// Hint shown when the runtime is missing
+    fn runtime_hint(&self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "Install Node.js first",
+            Ecosystem::Pipx => "Install pipx first",
+            Ecosystem::UvTool => "Install uv first",
+            Ecosystem::Cargo => "Install Rust from https://rustup.rs first",
+            Ecosystem::Go => "Install Go from https://go.dev/dl first",
+        }
+    }
+}
+
+/// Installs a tool with npm, pipx, uv, cargo or go
+#[derive(Debug, Clone)]
+pub struct EcosystemInstaller {
+    pub ecosystem: Ecosystem,
+    /// Package name, or the module path for `go`
+    pub package: String,
+    /// Version to install, latest when `None`
+    pub version: Option<String>,
+}
+
+impl EcosystemInstaller {
+    /// Create an installer, rejecting package names that could pass as options
+    pub fn new(ecosystem: Ecosystem, package: &str, version: Option<&str>) -> Result<Self, String> {
+        validate_package_name(package)?;
+        Ok(Self {
+            ecosystem,
+            package: package.to_string(),
+            version: version.map(|v| v.to_string()),
+        })
+    }
+
+    /// Command line installing the package
+    pub fn install_command(&self) -> Vec<String> {
+        let package = self.package.as_str();
+        let version = self.version.as_deref();
+        match self.ecosystem {
+            Ecosystem::Npm => args(&["npm", "install", "-g", &versioned(package, "@", version)]),
+            Ecosystem::Pipx => args(&["pipx", "install", &versioned(package, "==", version)]),
+            Ecosystem::UvTool => args(&["uv", "tool", "install", &versioned(package, "==", version)]),
+            Ecosystem::Cargo => match version {
+                Some(v) => args(&["cargo", "install", package, "--version", v]),
+                None => args(&["cargo", "install", package]),
+            },
+            Ecosystem::Go => args(&["go", "install", &format!("{}@{}", package, version.unwrap_or("latest"))]),
+        }
+    }
+
+    /// Command line upgrading the package to the latest (or pinned) version
+    pub fn upgrade_command(&self) -> Vec<String> {
+        let package = self.package.as_str();
+        match (self.ecosystem, self.version.as_deref()) {
+            (Ecosystem::Npm, None) => args(&["npm", "install", "-g", &format!("{}@latest", package)]),
+            (Ecosystem::Pipx, None) => args(&["pipx", "upgrade", package]),
+            (Ecosystem::UvTool, None) => args(&["uv", "tool", "upgrade", package]),
+            (Ecosystem::Cargo, None) => args(&["cargo", "install", package, "--force"]),
+            (Ecosystem::Cargo, Some(v)) => args(&["cargo", "install", package, "--version", v, "--force"]),
+            // Installing again replaces the installed version
+            _ => self.install_command(),
+        }
+    }
+
+    /// Command line removing the package
+    ///
+    /// `go` has no uninstall command, so its binary is deleted instead.
+    pub fn uninstall_command(&self) -> Option<Vec<String>> {
+        let package = self.package.as_str();
+        match self.ecosystem {
+            Ecosystem::Npm => Some(args(&["npm", "uninstall", "-g", package])),
+            Ecosystem::Pipx => Some(args(&["pipx", "uninstall", package])),
+            Ecosystem::UvTool => Some(args(&["uv", "tool", "uninstall", package])),
+            Ecosystem::Cargo => Some(args(&["cargo", "uninstall", package])),
+            Ecosystem::Go => None,
+        }
+    }
+
+    /// Command line listing installed packages, or inspecting the binary for `go`
+    pub fn query_command(&self) -> Result<Vec<String>, String> {
+        Ok(match self.ecosystem {
+            Ecosystem::Npm => args(&["npm", "ls", "-g", "--json", "--depth=0"]),
+            Ecosystem::Pipx => args(&["pipx", "list", "--json"]),
+            Ecosystem::UvTool => args(&["uv", "tool", "list"]),
+            Ecosystem::Cargo => args(&["cargo", "install", "--list"]),
+            Ecosystem::Go => {
+                let binary = go_binary_path(&self.package)?;
+                args(&["go", "version", "-m", &binary.to_string_lossy()])
+            }
+        })
+    }
+
+    /// Version of the package that is currently installed, if any
+    pub fn installed_version(&self) -> Result<Option<String>, String> {
+        if detection::which(self.ecosystem.runtime()).is_none() {
+            return Ok(None);
+        }
+        if self.ecosystem == Ecosystem::Go && !go_binary_path(&self.package)?.exists() {
+            return Ok(None);
+        }
+
+        let query = self.query_command()?;
+        let output = Command::new(&query[0])
+            .args(&query[1..])
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", query.join(" "), e))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // `npm ls` exits non-zero on unrelated problems but still prints the list
+        if !output.status.success() && self.ecosystem != Ecosystem::Npm {
+            return Ok(None);
+        }
+
+        Ok(match self.ecosystem {
+            Ecosystem::Npm => parse_npm_ls(&stdout, &self.package),
+            Ecosystem::Pipx => parse_pipx_list(&stdout, &self.package),
+            Ecosystem::UvTool => parse_uv_tool_list(&stdout, &self.package),
+            Ecosystem::Cargo => parse_cargo_install_list(&stdout, &self.package),
+            Ecosystem::Go => parse_go_version_m(&stdout, &self.package),
+        })
+    }
+
+    fn check_runtime(&self) -> Result<(), String> {
+        let runtime = self.ecosystem.runtime();
+        if detection::which(runtime).is_none() {
+            return Err(format!("{} is not installed. {}", runtime, self.ecosystem.runtime_hint()));
+        }
+        Ok(())
+    }
+
+    /// Run a command, e.g. `run_action(("Installing", "installed"), ...)`
+    fn run_action(&self, action: (&str, &str), command: Vec<String>, emit: &EventSink) -> Result<(), String> {
+        let (doing, done) = action;
+        emit(InstallEvent::Status {
+            message: format!("{} {} via {}...", doing, self.package, self.ecosystem.runtime()),
+        });
+        let result = self.check_runtime().and_then(|_| {
+            let mut cmd = command_from(&command)?;
+            cmd.envs(proxy_env_vars());
+            run_streamed(cmd, &command.join(" "), emit)
+        });
+        finish(emit, result, format!("Successfully {} {}", done, self.package))
+    }
+}
+
+impl Installer for EcosystemInstaller {
+    fn plan(&self) -> Vec<String> {
+        vec![self.install_command().join(" ")]
+    }
+
+    fn environment(&self) -> Vec<(String, String)> {
+        proxy_env_vars()
+    }
+
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        self.run_action(("Installing", "installed"), self.install_command(), emit)
+    }
+
+    fn verify(&self) -> Result<bool, String> {
+        Ok(self.installed_version()?.is_some())
+    }
+
+    fn upgrade(&self, emit: &EventSink) -> Result<(), String> {
+        self.run_action(("Upgrading", "upgraded"), self.upgrade_command(), emit)
+    }
+
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String> {
+        match self.uninstall_command() {
+            Some(command) => self.run_action(("Uninstalling", "uninstalled"), command, emit),
+            None => {
+                emit(InstallEvent::Status {
+                    message: format!("Uninstalling {}...", self.package),
+                });
+                let result = go_binary_path(&self.package).and_then(|binary| {
+                    std::fs::remove_file(&binary)
+                        .map_err(|e| format!("Failed to remove {}: {}", binary.display(), e))
+                });
+                finish(emit, result, format!("Successfully uninstalled {}", self.package))
+            }
+        }
+    }
+}
+
+fn args(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|p| p.to_string()).collect()
+}
+
+fn versioned(package: &str, separator: &str, version: Option<&str>) -> String {
+    match version {
+        Some(v) => format!("{}{}{}", package, separator, v),
+        None => package.to_string(),
+    }
+}
+
+/// Name of the binary `go install` builds for a module path
+///
+/// A trailing major version element is skipped, so
+/// `github.com/foo/bar/v2` builds `bar`.
+pub fn go_binary_name(module: &str) -> &str {
+    let mut parts = module.rsplit('/');
+    let last = parts.next().unwrap_or(module);
+    let is_major_version = last.len() > 1
+        && last.starts_with('v')
+        && last[1..].chars().all(|c| c.is_ascii_digit());
+    if is_major_version {
+        parts.next().unwrap_or(last)
+    } else {
+        last
+    }
+}
+
+/// Where `go install` puts the binary of a module: `$GOBIN`, else `$GOPATH/bin`
+fn go_binary_path(module: &str) -> Result<PathBuf, String> {
+    let output = Command::new("go")
+        .args(["env", "GOBIN", "GOPATH"])
+        .output()
+        .map_err(|e| format!("Failed to run go env: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let gobin = lines.next().unwrap_or("").trim();
+    let gopath = lines.next().unwrap_or("").trim();
+
+    let dir = if !gobin.is_empty() {
+        PathBuf::from(gobin)
+    } else if !gopath.is_empty() {
+        // GOPATH may hold several entries, binaries go to the first
+        let first = std::env::split_paths(gopath).next().unwrap_or_default();
+        first.join("bin")
+    } else {
+        return Err("Failed to find the Go binary directory".to_string());
+    };
+    Ok(dir.join(go_binary_name(module)))
+}
+
+/// Version of a package in `npm ls -g --json --depth=0` output
+pub fn parse_npm_ls(output: &str, package: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(output).ok()?;
+    value["dependencies"][package]["version"]
+        .as_str()
+        .map(|v| v.to_string())
+}
+
+/// Version of a package in `pipx list --json` output
+pub fn parse_pipx_list(output: &str, package: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(output).ok()?;
+    let venvs = value["venvs"].as_object()?;
+    venvs.values().find_map(|venv| {
+        let main = &venv["metadata"]["main_package"];
+        (main["package"].as_str() == Some(package))
+            .then(|| main["package_version"].as_str().map(|v| v.to_string()))
+            .flatten()
+    })
+}
+
+/// Version of a package in `uv tool list` output
+///
+/// Each tool is a line like `ruff v0.6.9`, followed by `- ruff` lines for its
+/// executables.
+pub fn parse_uv_tool_list(output: &str, package: &str) -> Option<String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with('-') && !line.starts_with(' '))
+        .find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next()? == package).then(|| parts.next().map(|v| v.trim_start_matches('v').to_string()))?
+        })
+}
+
+/// Version of a package in `cargo install --list` output
+///
+/// Each crate is a line like `ripgrep v14.1.0:`, followed by indented
+/// executable names. Crates installed from git or a path add the source in
+/// parentheses after the version.
+pub fn parse_cargo_install_list(output: &str, package: &str) -> Option<String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .find_map(|line| {
+            let mut parts = line.trim_end_matches(':').split_whitespace();
+            (parts.next()? == package).then(|| {
+                parts
+                    .next()
+                    .map(|v| v.trim_end_matches(':').trim_start_matches('v').to_string())
+            })?
+        })
+}
+
+/// Version of a module in `go version -m <binary>` output
+///
+/// The output has a `mod` line like `mod  golang.org/x/tools/gopls  v0.16.1  h1:...`
+/// for the main module of the binary.
+pub fn parse_go_version_m(output: &str, module: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? != "mod" {
+            return None;
+        }
+        let path = parts.next()?;
+        let version = parts.next()?;
+        module
+            .starts_with(path)
+            .then(|| version.trim_start_matches('v').to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installer(ecosystem: Ecosystem, package: &str, version: Option<&str>) -> EcosystemInstaller {
+        EcosystemInstaller {
+            ecosystem,
+            package: package.to_string(),
+            version: version.map(|v| v.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_option_like_package_names_are_rejected() {
+        assert!(EcosystemInstaller::new(Ecosystem::Npm, "--prefix=/tmp/evil", None).is_err());
+        assert!(EcosystemInstaller::new(Ecosystem::Cargo, "--git", None).is_err());
+        assert!(EcosystemInstaller::new(Ecosystem::Pipx, "ruff tool", None).is_err());
+        assert!(EcosystemInstaller::new(Ecosystem::Npm, "@google/gemini-cli", None).is_ok());
+        assert!(EcosystemInstaller::new(Ecosystem::Go, "golang.org/x/tools/gopls", None).is_ok());
+    }
+
+    #[test]
+    fn test_install_commands() {
+        let cases = [
+            (installer(Ecosystem::Npm, "@google/gemini-cli", None), "npm install -g @google/gemini-cli"),
+            (installer(Ecosystem::Npm, "ccusage", Some("15.2.0")), "npm install -g ccusage@15.2.0"),
+            (installer(Ecosystem::Pipx, "ruff", Some("0.6.9")), "pipx install ruff==0.6.9"),
+            (installer(Ecosystem::UvTool, "ruff", None), "uv tool install ruff"),
+            (installer(Ecosystem::Cargo, "ripgrep", Some("14.1.0")), "cargo install ripgrep --version 14.1.0"),
+            (installer(Ecosystem::Go, "golang.org/x/tools/gopls", None), "go install golang.org/x/tools/gopls@latest"),
+        ];
+
+        for (installer, expected) in cases {
+            assert_eq!(installer.install_command().join(" "), expected);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_and_uninstall_commands() {
+        let npm = installer(Ecosystem::Npm, "ccusage", None);
+        assert_eq!(npm.upgrade_command().join(" "), "npm install -g ccusage@latest");
+        assert_eq!(npm.uninstall_command().unwrap().join(" "), "npm uninstall -g ccusage");
+
+        let cargo = installer(Ecosystem::Cargo, "ripgrep", None);
+        assert_eq!(cargo.upgrade_command().join(" "), "cargo install ripgrep --force");
+
+        let uv = installer(Ecosystem::UvTool, "ruff", None);
+        assert_eq!(uv.upgrade_command().join(" "), "uv tool upgrade ruff");
+
+        assert!(installer(Ecosystem::Go, "golang.org/x/tools/gopls", None).uninstall_command().is_none());
+    }
+
+    #[test]
+    fn test_parse_installed_versions() {
+        let npm = r#"{"dependencies":{"ccusage":{"version":"15.2.0"},"@google/gemini-cli":{"version":"0.9.0"}}}"#;
+        assert_eq!(parse_npm_ls(npm, "@google/gemini-cli").as_deref(), Some("0.9.0"));
+        assert_eq!(parse_npm_ls(npm, "typescript"), None);
+
+        let pipx = r#"{"venvs":{"ruff":{"metadata":{"main_package":{"package":"ruff","package_version":"0.6.9"}}}}}"#;
+        assert_eq!(parse_pipx_list(pipx, "ruff").as_deref(), Some("0.6.9"));
+
+        let uv = "black v24.8.0\n- black\n- blackd\nruff v0.6.9\n- ruff\n";
+        assert_eq!(parse_uv_tool_list(uv, "ruff").as_deref(), Some("0.6.9"));
+        assert_eq!(parse_uv_tool_list(uv, "blackd"), None);
+
+        let cargo = "bat v0.24.0:\n    bat\nripgrep v14.1.0:\n    rg\ntool v0.1.0 (/home/me/tool):\n    tool\n";
+        assert_eq!(parse_cargo_install_list(cargo, "ripgrep").as_deref(), Some("14.1.0"));
+        assert_eq!(parse_cargo_install_list(cargo, "tool").as_deref(), Some("0.1.0"));
+        assert_eq!(parse_cargo_install_list(cargo, "rg"), None);
+
+        let go = "/home/me/go/bin/gopls: go1.23.1\n\tpath\tgolang.org/x/tools/gopls\n\tmod\tgolang.org/x/tools/gopls\tv0.16.1\th1:abc=\n";
+        assert_eq!(parse_go_version_m(go, "golang.org/x/tools/gopls").as_deref(), Some("0.16.1"));
+    }
+
+    #[test]
+    fn test_go_binary_name() {
+        assert_eq!(go_binary_name("golang.org/x/tools/gopls"), "gopls");
+        assert_eq!(go_binary_name("github.com/foo/bar/v2"), "bar");
+        assert_eq!(go_binary_name("github.com/foo/v8engine"), "v8engine");
+    }
+}
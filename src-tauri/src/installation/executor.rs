@@ -0,0 +1,202 @@
+use crate::dependencies::spec;
+use crate::installation::installer::{self, EventSink};
+use crate::types::{CliToolTemplate, ToolInstallOutcome, ToolInstallStatus};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Default number of tools installed at the same time
+pub const DEFAULT_MAX_PARALLEL: usize = 3;
+
+/// Install dependency waves, running the tools of each wave concurrently
+///
+/// # Arguments
+/// * `waves` - Layered plan from `resolve_installation_waves`
+/// * `max_parallel` - Maximum number of installs running at the same time
+/// * `uses_brew` - Whether a tool installs through Homebrew. Homebrew holds a
+///   global lock, so these installs never run concurrently with each other
+/// * `dependencies` - IDs of the tools a tool depends on, see [`dependency_ids`]
+/// * `install` - Installs a single tool
+///
+/// # Returns
+/// * `Ok(Vec<ToolInstallOutcome>)` with one outcome per tool, in wave order.
+///   Tools that depend on a failed tool, directly or through other tools, are
+///   skipped; independent tools are still installed.
+/// * `Err(String)` if the worker pool can't be created
+pub fn execute_waves<B, D, I>(
+    waves: &[Vec<String>],
+    max_parallel: usize,
+    uses_brew: B,
+    dependencies: D,
+    install: I,
+) -> Result<Vec<ToolInstallOutcome>, String>
+where
+    B: Fn(&str) -> bool + Sync,
+    D: Fn(&str) -> Vec<String>,
+    I: Fn(&str) -> Result<(), String> + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(max_parallel.max(1))
+        .build()
+        .map_err(|e| format!("Failed to create install worker pool: {}", e))?;
+
+    let brew_lock = Mutex::new(());
+    let mut outcomes = Vec::new();
+    // Tools that failed or were skipped; their dependents are skipped too
+    let mut blocked: HashSet<String> = HashSet::new();
+
+    for (wave_idx, wave) in waves.iter().enumerate() {
+        let mut runnable = Vec::new();
+        for id in wave {
+            match dependencies(id).into_iter().find(|dep| blocked.contains(dep)) {
+                Some(dep) => outcomes.push(ToolInstallOutcome {
+                    tool_id: id.clone(),
+                    wave: wave_idx,
+                    status: ToolInstallStatus::Skipped,
+                    error: Some(format!("Skipped because {} was not installed", dep)),
+                }),
+                None => runnable.push(id),
+            }
+        }
+
+        let wave_outcomes: Vec<ToolInstallOutcome> = pool.install(|| {
+            runnable
+                .par_iter()
+                .map(|id| {
+                    let _guard = if uses_brew(id) {
+                        Some(brew_lock.lock().unwrap_or_else(|e| e.into_inner()))
+                    } else {
+                        None
+                    };
+
+                    let result = install(id);
+                    ToolInstallOutcome {
+                        tool_id: id.to_string(),
+                        wave: wave_idx,
+                        status: if result.is_ok() {
+                            ToolInstallStatus::Installed
+                        } else {
+                            ToolInstallStatus::Failed
+                        },
+                        error: result.err(),
+                    }
+                })
+                .collect()
+        });
+
+        outcomes.extend(wave_outcomes);
+        blocked.extend(
+            outcomes
+                .iter()
+                .filter(|o| o.wave == wave_idx && o.status != ToolInstallStatus::Installed)
+                .map(|o| o.tool_id.clone()),
+        );
+    }
+
+    // Keep the outcomes of each wave in plan order
+    outcomes.sort_by_key(|o| {
+        let position = waves[o.wave].iter().position(|id| *id == o.tool_id);
+        (o.wave, position)
+    });
+    Ok(outcomes)
+}
+
+/// IDs of every tool a template depends on, including alternatives and
+/// optional dependencies, since all of them are ordered before the template
+pub fn dependency_ids(template: &CliToolTemplate) -> Vec<String> {
+    spec::dependency_specs(template)
+        .iter()
+        .flat_map(|dep| dep.ids().map(|id| id.to_string()).collect::<Vec<_>>())
+        .collect()
+}
+
+/// Check whether a template installs through Homebrew
+pub fn uses_brew(template: &CliToolTemplate) -> bool {
+    template
+        .install_methods
+        .as_ref()
+        .and_then(|methods| methods.get(installer::default_method_index(template)))
+        .map(|m| m.method_type == "brew")
+        .unwrap_or(false)
+}
+
+/// Install a tool using the default install method of its template
+///
+/// # Arguments
+/// * `template` - The template of the tool to install
+/// * `emit` - Sink receiving progress events
+pub fn install_from_template(template: &CliToolTemplate, emit: &EventSink) -> Result<(), String> {
+    installer::installer_for(template, installer::default_method_index(template))?.install(emit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    fn waves(layers: &[&[&str]]) -> Vec<Vec<String>> {
+        layers
+            .iter()
+            .map(|wave| wave.iter().map(|s| s.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_brew_installs_never_overlap() {
+        let plan = waves(&[&["a", "b", "c", "d"]]);
+        let running = AtomicUsize::new(0);
+        let max_seen = AtomicUsize::new(0);
+
+        let outcomes = execute_waves(&plan, 4, |_| true, |_| Vec::new(), |_| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_seen.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(max_seen.load(Ordering::SeqCst), 1);
+        assert!(outcomes.iter().all(|o| o.status == ToolInstallStatus::Installed));
+    }
+
+    #[test]
+    fn test_failure_skips_later_waves() {
+        // c needs b, e needs c, d is independent of the failed tool
+        let plan = waves(&[&["a", "b"], &["c", "d"], &["e"]]);
+        let dependencies = |id: &str| -> Vec<String> {
+            match id {
+                "c" => vec!["a".to_string(), "b".to_string()],
+                "d" => vec!["a".to_string()],
+                "e" => vec!["c".to_string()],
+                _ => Vec::new(),
+            }
+        };
+
+        let outcomes = execute_waves(&plan, 2, |_| false, dependencies, |id| {
+            if id == "b" {
+                Err("boom".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+
+        let statuses: Vec<(&str, ToolInstallStatus)> = outcomes
+            .iter()
+            .map(|o| (o.tool_id.as_str(), o.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("a", ToolInstallStatus::Installed),
+                ("b", ToolInstallStatus::Failed),
+                ("c", ToolInstallStatus::Skipped),
+                ("d", ToolInstallStatus::Installed),
+                ("e", ToolInstallStatus::Skipped),
+            ]
+        );
+    }
+}
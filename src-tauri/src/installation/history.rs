@@ -0,0 +1,341 @@
+use crate::installation::installer::InstallEvent;
+use crate::installation::jobs;
+use crate::types::{InstallHistorySettings, InstallJobRecord, InstallJobStatus};
+use chrono::{DateTime, Duration, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Jobs kept when the settings don't say otherwise
+pub const DEFAULT_MAX_JOBS: usize = 100;
+/// Age in days after which jobs are removed when the settings don't say otherwise
+pub const DEFAULT_MAX_AGE_DAYS: u32 = 30;
+
+/// Get the directory where install jobs are stored
+fn get_history_dir() -> Result<PathBuf, String> {
+    let mut dir = dirs::data_dir()
+        .ok_or("Failed to find data directory")?;
+
+    dir.push("dev-env-helper");
+    dir.push("install-history");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create install history directory: {}", e))?;
+    }
+
+    Ok(dir)
+}
+
+fn record_path(dir: &Path, job_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", job_id))
+}
+
+fn log_path(dir: &Path, job_id: &str) -> PathBuf {
+    dir.join(format!("{}.log", job_id))
+}
+
+fn write_record(dir: &Path, record: &InstallJobRecord) -> Result<(), String> {
+    let path = record_path(dir, &record.job_id);
+    let json = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize install job: {}", e))?;
+
+    fs::write(&path, json)
+        .map_err(|e| format!("Failed to write install job {}: {}", path.display(), e))
+}
+
+/// Persists one install job while it runs
+///
+/// The record is written when the job starts and again when it finishes;
+/// output is appended to the log as it arrives. History is best effort, so
+/// write failures never fail the install itself.
+#[derive(Debug)]
+pub struct JobRecorder {
+    dir: Option<PathBuf>,
+    record: Mutex<InstallJobRecord>,
+    log: Mutex<Option<File>>,
+}
+
+impl JobRecorder {
+    /// Start recording a job in the app's history directory
+    pub fn start(job_id: &str, tool: Option<String>, method: Option<String>) -> Self {
+        Self::start_in(get_history_dir().ok(), job_id, tool, method)
+    }
+
+    fn start_in(dir: Option<PathBuf>, job_id: &str, tool: Option<String>, method: Option<String>) -> Self {
+        let record = InstallJobRecord {
+            job_id: job_id.to_string(),
+            tool,
+            method,
+            commands: Vec::new(),
+            started_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+            status: InstallJobStatus::Running,
+            error: None,
+        };
+
+        let log = dir.as_ref().and_then(|dir| {
+            let _ = write_record(dir, &record);
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log_path(dir, job_id))
+                .ok()
+        });
+
+        Self {
+            dir,
+            record: Mutex::new(record),
+            log: Mutex::new(log),
+        }
+    }
+
+    /// Append an event to the output log
+    ///
+    /// Jobs installing several tools pass the tool an event belongs to, which
+    /// prefixes its log line.
+    pub fn log(&self, tool: Option<&str>, event: &InstallEvent) {
+        let Some(mut line) = log_line(event) else {
+            return;
+        };
+        if let Some(tool) = tool {
+            line = format!("[{}] {}", tool, line);
+        }
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = log.as_mut() {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
+    /// Store the outcome of the job
+    pub fn finish(&self, status: InstallJobStatus, commands: Vec<String>, error: Option<String>) {
+        let mut record = self.record.lock().unwrap_or_else(|e| e.into_inner());
+        record.commands = commands;
+        record.finished_at = Some(Utc::now().to_rfc3339());
+        record.status = status;
+        record.error = error;
+
+        if let Some(dir) = &self.dir {
+            let _ = write_record(dir, &record);
+        }
+    }
+}
+
+/// Render an event as a line of the output log
+fn log_line(event: &InstallEvent) -> Option<String> {
+    match event {
+        InstallEvent::Output { line, .. } => Some(line.clone()),
+        InstallEvent::Status { message } | InstallEvent::Success { message } => {
+            Some(format!("==> {}", message))
+        }
+        InstallEvent::Error { message } => Some(format!("error: {}", message)),
+        InstallEvent::Cancelled { message, .. } => Some(format!("cancelled: {}", message)),
+        InstallEvent::Verification(verification) => Some(format!(
+            "==> Checksum {:?}: sha256 {}",
+            verification.status, verification.actual
+        )),
+        InstallEvent::Started { .. } | InstallEvent::Progress { .. } => None,
+    }
+}
+
+/// List recorded install jobs, newest first
+pub fn list_install_history() -> Result<Vec<InstallJobRecord>, String> {
+    let dir = get_history_dir()?;
+    fail_interrupted_in(&dir, jobs::is_running)?;
+    list_history_in(&dir)
+}
+
+/// Mark jobs that are still recorded as running, but aren't, as failed
+///
+/// This happens when the app quit or crashed during an install, which leaves
+/// the record behind as `Running` forever.
+fn fail_interrupted_in(dir: &Path, is_running: impl Fn(&str) -> bool) -> Result<(), String> {
+    for mut record in list_history_in(dir)? {
+        if record.status == InstallJobStatus::Running && !is_running(&record.job_id) {
+            record.status = InstallJobStatus::Failed;
+            record.error = Some("Interrupted before the install finished".to_string());
+            write_record(dir, &record)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `job_id` has the form of the IDs given out by [`jobs::start_job`],
+/// `job-<millis>-<counter>`
+fn is_valid_job_id(job_id: &str) -> bool {
+    let Some(rest) = job_id.strip_prefix("job-") else {
+        return false;
+    };
+    let mut parts = rest.split('-');
+    let digits = |part: Option<&str>| {
+        part.is_some_and(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+    };
+    digits(parts.next()) && digits(parts.next()) && parts.next().is_none()
+}
+
+fn list_history_in(dir: &Path) -> Result<Vec<InstallJobRecord>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read install history directory: {}", e))?;
+
+    let mut records: Vec<InstallJobRecord> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|path| fs::read_to_string(&path).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+
+    records.sort_by_key(|record| std::cmp::Reverse(started_at(record)));
+    Ok(records)
+}
+
+fn started_at(record: &InstallJobRecord) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&record.started_at)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Read the full output log of a job
+pub fn get_install_log(job_id: &str) -> Result<String, String> {
+    if !is_valid_job_id(job_id) {
+        return Err(format!("Invalid install job ID '{}'", job_id));
+    }
+    let dir = get_history_dir()?;
+    if !record_path(&dir, job_id).exists() {
+        return Err(format!("Install job '{}' not found", job_id));
+    }
+
+    let path = log_path(&dir, job_id);
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read install log {}: {}", path.display(), e))
+}
+
+/// Remove finished jobs that fall outside the configured retention
+///
+/// Interrupted jobs count as finished, so they are removed as well.
+pub fn prune_install_history(settings: Option<&InstallHistorySettings>) -> Result<(), String> {
+    let dir = get_history_dir()?;
+    fail_interrupted_in(&dir, jobs::is_running)?;
+    prune_history_in(&dir, settings, Utc::now())
+}
+
+fn prune_history_in(
+    dir: &Path,
+    settings: Option<&InstallHistorySettings>,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    let max_jobs = settings.and_then(|s| s.max_jobs).unwrap_or(DEFAULT_MAX_JOBS);
+    let max_age_days = settings.and_then(|s| s.max_age_days).unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    let cutoff = now - Duration::days(i64::from(max_age_days));
+
+    let finished = list_history_in(dir)?
+        .into_iter()
+        .filter(|record| record.status != InstallJobStatus::Running);
+
+    for (idx, record) in finished.enumerate() {
+        let expired = started_at(&record).is_none_or(|t| t < cutoff);
+        if idx >= max_jobs || expired {
+            let _ = fs::remove_file(record_path(dir, &record.job_id));
+            let _ = fs::remove_file(log_path(dir, &record.job_id));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installation::installer::OutputStream;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("install-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_recorder_persists_record_and_log() {
+        let dir = scratch_dir("recorder");
+        let recorder = JobRecorder::start_in(Some(dir.clone()), "job-1", Some("node".to_string()), Some("brew".to_string()));
+
+        recorder.log(None, &InstallEvent::Status { message: "Installing node...".to_string() });
+        recorder.log(None, &InstallEvent::Progress { downloaded: 1, total: 2, resumed_from: 0 });
+        recorder.log(Some("node"), &InstallEvent::output("Pouring node".to_string(), OutputStream::Stdout));
+        assert_eq!(list_history_in(&dir).unwrap()[0].status, InstallJobStatus::Running);
+
+        recorder.finish(InstallJobStatus::Failed, vec!["brew install node".to_string()], Some("exit code 1".to_string()));
+
+        let records = list_history_in(&dir).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tool.as_deref(), Some("node"));
+        assert_eq!(records[0].status, InstallJobStatus::Failed);
+        assert_eq!(records[0].commands, vec!["brew install node"]);
+        assert!(records[0].finished_at.is_some());
+        assert_eq!(
+            fs::read_to_string(log_path(&dir, "job-1")).unwrap(),
+            "==> Installing node...\n[node] Pouring node\n"
+        );
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_prune_applies_count_and_age_limits() {
+        let dir = scratch_dir("prune");
+        let now = Utc::now();
+        let record = |id: &str, days_ago: i64, status: InstallJobStatus| InstallJobRecord {
+            job_id: id.to_string(),
+            tool: None,
+            method: None,
+            commands: vec![],
+            started_at: (now - Duration::days(days_ago)).to_rfc3339(),
+            finished_at: None,
+            status,
+            error: None,
+        };
+        write_record(&dir, &record("new", 0, InstallJobStatus::Succeeded)).unwrap();
+        write_record(&dir, &record("older", 1, InstallJobStatus::Failed)).unwrap();
+        write_record(&dir, &record("oldest", 2, InstallJobStatus::Succeeded)).unwrap();
+        write_record(&dir, &record("stale", 40, InstallJobStatus::Succeeded)).unwrap();
+        write_record(&dir, &record("running", 50, InstallJobStatus::Running)).unwrap();
+        fs::write(log_path(&dir, "oldest"), "output").unwrap();
+
+        let settings = InstallHistorySettings { max_jobs: Some(2), max_age_days: None };
+        prune_history_in(&dir, Some(&settings), now).unwrap();
+
+        let ids: Vec<String> = list_history_in(&dir).unwrap().into_iter().map(|r| r.job_id).collect();
+        assert_eq!(ids, vec!["new", "older", "running"]);
+        assert!(!log_path(&dir, "oldest").exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_interrupted_jobs_are_marked_failed() {
+        let dir = scratch_dir("interrupted");
+        JobRecorder::start_in(Some(dir.clone()), "job-1-0", None, None);
+        JobRecorder::start_in(Some(dir.clone()), "job-1-1", None, None);
+
+        fail_interrupted_in(&dir, |id| id == "job-1-1").unwrap();
+
+        let records = list_history_in(&dir).unwrap();
+        let status = |id: &str| records.iter().find(|r| r.job_id == id).unwrap().status;
+        assert_eq!(status("job-1-0"), InstallJobStatus::Failed);
+        assert_eq!(status("job-1-1"), InstallJobStatus::Running);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_job_id_validation() {
+        assert!(is_valid_job_id("job-1718000000000-3"));
+        assert!(!is_valid_job_id("job-1"));
+        assert!(!is_valid_job_id("job-1-2-3"));
+        assert!(!is_valid_job_id("job-1-"));
+        assert!(!is_valid_job_id("job-../../etc/passwd"));
+        assert!(!is_valid_job_id("../job-1-2"));
+    }
+}
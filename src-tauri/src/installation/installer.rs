@@ -0,0 +1,735 @@
+use crate::cli::{detection, template};
+use crate::dependencies::resolution;
+use crate::installation::brew::BrewInstaller;
+use crate::installation::checksum::ChecksumSource;
+use crate::installation::download::{self, DmgInstaller, GitHubReleaseInstaller};
+use crate::installation::ecosystem::{Ecosystem, EcosystemInstaller};
+use crate::installation::jobs;
+use crate::installation::manifest;
+use crate::installation::runtime_manager::{RuntimeInstaller, RuntimeManager};
+use crate::installation::script::ScriptInstaller;
+use crate::installation::system_package::{self, SystemPackageInstaller};
+use crate::types::{ChecksumVerification, CliToolTemplate, InstallMethod};
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Component, Path};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// Events emitted while installing or uninstalling a tool
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum InstallEvent {
+    /// First event of a cancellable install
+    Started {
+        #[serde(rename = "jobId")]
+        job_id: String,
+    },
+    Status { message: String },
+    Output {
+        line: String,
+        stream: OutputStream,
+        /// When the line was read, RFC 3339
+        timestamp: String,
+    },
+    /// Result of checking a download against its checksum
+    Verification(ChecksumVerification),
+    Progress {
+        downloaded: u64,
+        total: u64,
+        /// Offset a resumed download continued from, 0 for a fresh download
+        #[serde(rename = "resumedFrom")]
+        resumed_from: u64,
+    },
+    Success { message: String },
+    Error { message: String },
+    /// The install was stopped through `cancel_install`
+    Cancelled {
+        message: String,
+        #[serde(rename = "completedCommands")]
+        completed_commands: Vec<String>,
+    },
+}
+
+/// Output stream of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl InstallEvent {
+    /// An output event for a line read just now
+    pub fn output(line: String, stream: OutputStream) -> Self {
+        InstallEvent::Output {
+            line,
+            stream,
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        }
+    }
+
+    /// Convert the event into the JSON payload sent to the frontend
+    pub fn to_payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Receives install events, from any thread
+pub type EventSink = Arc<dyn Fn(InstallEvent) + Send + Sync>;
+
+/// A backend that can install a tool
+///
+/// Backends are picked from an `InstallMethod` by [`installer_for`], which is
+/// the only place that needs to change when a new backend is added.
+pub trait Installer: Send + Sync {
+    /// Human-readable steps that `install` would run
+    fn plan(&self) -> Vec<String>;
+
+    /// Environment variables `install` sets for the commands it runs
+    fn environment(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Install the tool, reporting progress to `emit`
+    fn install(&self, emit: &EventSink) -> Result<(), String>;
+
+    /// Check whether the tool is currently installed
+    fn verify(&self) -> Result<bool, String>;
+
+    /// Upgrade the tool to the latest version, reporting progress to `emit`
+    fn upgrade(&self, _emit: &EventSink) -> Result<(), String> {
+        Err("This install method doesn't support upgrades".to_string())
+    }
+
+    /// Remove the tool, reporting progress to `emit`
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String>;
+}
+
+/// Create the installer for one install method of a template
+///
+/// # Arguments
+/// * `template` - The template of the tool
+/// * `method_index` - Index into `template.install_methods`
+pub fn installer_for(
+    template: &CliToolTemplate,
+    method_index: usize,
+) -> Result<Box<dyn Installer>, String> {
+    let method = template
+        .install_methods
+        .as_ref()
+        .and_then(|methods| methods.get(method_index))
+        .ok_or_else(|| format!("'{}' has no install method #{}", template.id, method_index))?;
+
+    installer_for_method(template, method)
+}
+
+fn checksum_source(method: &InstallMethod) -> ChecksumSource {
+    ChecksumSource {
+        sha256: method.sha256.clone(),
+        checksum_url: method.checksum_url.clone(),
+        checksum_pattern: method.checksum_pattern.clone(),
+    }
+}
+
+fn installer_for_method(
+    template: &CliToolTemplate,
+    method: &InstallMethod,
+) -> Result<Box<dyn Installer>, String> {
+    let executable = template.executable.clone();
+
+    match method.method_type.as_str() {
+        "brew" => {
+            let tap = method.brew_tap.as_deref();
+            match (&method.brew_cask_name, &method.brew_formula_name) {
+                (Some(cask), _) => Ok(Box::new(BrewInstaller::new(tap, cask, true))),
+                (None, Some(formula)) => Ok(Box::new(BrewInstaller::new(tap, formula, false))),
+                (None, None) => Err(format!(
+                    "Brew install method for '{}' has no package name",
+                    template.id
+                )),
+            }
+        }
+        "script" => {
+            let commands = method.script_commands.clone().ok_or_else(|| {
+                format!("Script install method for '{}' has no commands", template.id)
+            })?;
+            Ok(Box::new(ScriptInstaller {
+                commands,
+                uninstall_commands: method.uninstall_commands.clone(),
+                executable,
+            }))
+        }
+        "dmg" => {
+            let url = method.dmg_url.clone().ok_or_else(|| {
+                format!("DMG install method for '{}' has no URL", template.id)
+            })?;
+            Ok(Box::new(DmgInstaller {
+                url,
+                checksum: checksum_source(method),
+                executable,
+            }))
+        }
+        "github" => {
+            let repo = method.github_repo.clone().ok_or_else(|| {
+                format!("GitHub install method for '{}' has no repository", template.id)
+            })?;
+            Ok(Box::new(GitHubReleaseInstaller {
+                tool_id: template.id.clone(),
+                repo,
+                asset_pattern: method.github_asset_pattern.clone(),
+                checksum: checksum_source(method),
+                executable,
+                binary_path: method.binary_path.clone(),
+            }))
+        }
+        "system" => {
+            let release = system_package::read_os_release()?;
+            let escalation = system_package::configured_escalation();
+            Ok(Box::new(SystemPackageInstaller::for_method(method, &release, escalation)?))
+        }
+        "runtime-manager" => {
+            let runtime = method.runtime_name.as_deref().ok_or_else(|| {
+                format!("Runtime manager install method for '{}' has no runtime name", template.id)
+            })?;
+            let manager = match method.runtime_manager.as_deref() {
+                Some(name) => RuntimeManager::from_name(name)
+                    .ok_or_else(|| format!("Unknown runtime manager '{}'", name))?,
+                None => RuntimeManager::detect().ok_or("Neither mise nor asdf is installed")?,
+            };
+            Ok(Box::new(RuntimeInstaller::new(manager, runtime, method.package_version.as_deref())))
+        }
+        other => match Ecosystem::from_method_type(other) {
+            Some(ecosystem) => {
+                let package = method.package_name.as_deref().ok_or_else(|| {
+                    format!("{} install method for '{}' has no package name", other, template.id)
+                })?;
+                Ok(Box::new(EcosystemInstaller::new(
+                    ecosystem,
+                    package,
+                    method.package_version.as_deref(),
+                )?))
+            }
+            None => Err(format!(
+                "Install method '{}' of '{}' is not supported",
+                other, template.id
+            )),
+        },
+    }
+}
+
+/// Index of the install method to use when the caller doesn't pick one
+///
+/// On Linux a "system" method is preferred when the host's package manager is
+/// supported, since the other builtin methods target macOS.
+pub fn default_method_index(template: &CliToolTemplate) -> usize {
+    let methods = template.install_methods.as_deref().unwrap_or_default();
+
+    if cfg!(target_os = "linux") && system_package::detect_package_manager().is_ok() {
+        if let Some(idx) = methods.iter().position(|m| m.method_type == "system") {
+            return idx;
+        }
+    }
+    0
+}
+
+/// Install a tool by template ID using one of its install methods
+///
+/// Without a `method_index`, the method from [`default_method_index`] is used.
+pub fn install_tool(template_id: &str, method_index: Option<usize>, emit: &EventSink) -> Result<(), String> {
+    let template = template::get_template_by_id(template_id)
+        .ok_or_else(|| format!("Unknown tool ID: {}", template_id))?;
+    let method_index = method_index.unwrap_or_else(|| default_method_index(&template));
+    installer_for(&template, method_index)?.install(emit)
+}
+
+/// Index of the install method a tool was most likely installed with
+///
+/// This is the method that placed the tool's files if the app recorded one,
+/// else the method from [`default_method_index`].
+pub fn active_method_index(template: &CliToolTemplate) -> usize {
+    recorded_method_index(template)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| default_method_index(template))
+}
+
+/// Upgrade an installed tool through the backend of its active install method
+pub fn upgrade_tool(template: &CliToolTemplate, emit: &EventSink) -> Result<(), String> {
+    installer_for(template, active_method_index(template))?.upgrade(emit)
+}
+
+/// Uninstall a tool by template ID using one of its install methods
+///
+/// Without a `method_index`, the method from [`active_method_index`] is used.
+///
+/// # Arguments
+/// * `template_id` - The tool to remove
+/// * `method_index` - Index into the template's install methods
+/// * `force` - Uninstall even if installed tools depend on this one
+/// * `purge_config` - Also delete the template's config files
+/// * `emit` - Sink receiving progress events
+pub fn uninstall_tool(
+    template_id: &str,
+    method_index: Option<usize>,
+    force: bool,
+    purge_config: bool,
+    emit: &EventSink,
+) -> Result<(), String> {
+    let templates = template::list_all_templates();
+    let template = templates
+        .iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| format!("Unknown tool ID: {}", template_id))?;
+
+    if !force {
+        let installed: HashSet<String> = detection::detect_cli_tools(&templates)
+            .into_iter()
+            .filter(|d| d.installed)
+            .map(|d| d.template_id)
+            .collect();
+        let dependents = installed_dependents(template_id, &templates, &installed);
+        if !dependents.is_empty() {
+            let message = format!(
+                "{} is required by {}. Uninstall those first or force the removal",
+                template.name,
+                dependents.join(", ")
+            );
+            emit(InstallEvent::Error { message: message.clone() });
+            return Err(message);
+        }
+    }
+
+    let method_index = method_index.unwrap_or_else(|| active_method_index(template));
+    installer_for(template, method_index)?.uninstall(emit)?;
+
+    if purge_config {
+        let result = purge_config_files(template, emit);
+        finish(emit, result, format!("Removed the config files of {}", template.name))?;
+    }
+    Ok(())
+}
+
+/// Names of installed tools that depend on `tool_id`
+fn installed_dependents(
+    tool_id: &str,
+    templates: &[CliToolTemplate],
+    installed: &HashSet<String>,
+) -> Vec<String> {
+    resolution::get_reverse_dependencies_with_names(tool_id, templates)
+        .into_iter()
+        .filter(|(id, _)| installed.contains(id))
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Index of the install method recorded in the install manifest, if any
+fn recorded_method_index(template: &CliToolTemplate) -> Result<Option<usize>, String> {
+    let Some(entry) = manifest::get_entry(&template.id)? else {
+        return Ok(None);
+    };
+    Ok(template
+        .install_methods
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .position(|m| m.method_type == entry.method))
+}
+
+fn purge_config_files(template: &CliToolTemplate, emit: &EventSink) -> Result<(), String> {
+    let home = dirs::home_dir().ok_or("Failed to find home directory")?;
+    let files: Vec<String> = template
+        .config_files
+        .iter()
+        .map(|config| detection::expand_path(&config.path))
+        .collect();
+    // Check every path before removing any, so a bad template removes nothing
+    for file in &files {
+        check_purgeable(Path::new(file), &home)?;
+    }
+    download::remove_files(&files, emit)
+}
+
+/// Refuse to purge anything but a regular file or symlink below `home`
+///
+/// Config file paths come from templates, which may be custom or imported,
+/// so they can't be trusted to point at a single config file.
+fn check_purgeable(path: &Path, home: &Path) -> Result<(), String> {
+    if path.components().any(|c| c == Component::ParentDir) {
+        return Err(format!("Refusing to purge {}: the path contains '..'", path.display()));
+    }
+    if !path.starts_with(home) || path == home {
+        return Err(format!(
+            "Refusing to purge {}: config files must be inside the home directory",
+            path.display()
+        ));
+    }
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => Err(format!(
+            "Refusing to purge {}: it is a directory",
+            path.display()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Type of the install method [`install_tool`] would use, e.g. "brew"
+pub fn method_type_for(template_id: &str, method_index: Option<usize>) -> Option<String> {
+    let template = template::get_template_by_id(template_id)?;
+    let method_index = method_index.unwrap_or_else(|| default_method_index(&template));
+    template
+        .install_methods?
+        .get(method_index)
+        .map(|method| method.method_type.clone())
+}
+
+/// Emit the final success or error event for an operation and pass its result on
+pub fn finish(emit: &EventSink, result: Result<(), String>, success_message: String) -> Result<(), String> {
+    match &result {
+        Ok(()) => emit(InstallEvent::Success {
+            message: success_message,
+        }),
+        Err(message) => match jobs::current().filter(|job| job.is_cancelled()) {
+            Some(job) => emit(InstallEvent::Cancelled {
+                message: message.clone(),
+                completed_commands: job.completed_commands(),
+            }),
+            None => emit(InstallEvent::Error {
+                message: message.clone(),
+            }),
+        },
+    }
+    result
+}
+
+/// Number of trailing stderr lines included in the error of a failed command
+pub const STDERR_TAIL_LINES: usize = 20;
+
+/// Run a command, streaming its stdout and stderr lines to `emit` as output events
+///
+/// Both pipes are drained concurrently so a chatty stream can't block the
+/// child. `what` names the command in error messages, e.g. "brew install";
+/// errors end with the last [`STDERR_TAIL_LINES`] lines of stderr. When the
+/// calling thread runs a [`jobs::Job`], the command can be cancelled through it.
+pub fn run_streamed(mut cmd: Command, what: &str, emit: &EventSink) -> Result<(), String> {
+    let job = jobs::current();
+    if job.as_ref().is_some_and(|job| job.is_cancelled()) {
+        return Err(jobs::CANCELLED.to_string());
+    }
+    jobs::isolate_process_group(&mut cmd);
+    let command_line = command_line(&cmd);
+    if let Some(job) = &job {
+        job.record_started(command_line.clone());
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn {}: {}", what, e))?;
+    let pid = child.id();
+    if let Some(job) = &job {
+        job.register_process(pid);
+    }
+
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    let stdout_thread = stream_lines(stdout, OutputStream::Stdout, emit);
+    let stderr_thread = stream_lines(stderr, OutputStream::Stderr, emit);
+
+    let status = child.wait()
+        .map_err(|e| format!("Failed to wait for {}: {}", what, e));
+    if let Some(job) = &job {
+        job.unregister_process(pid);
+    }
+    let status = status?;
+
+    stdout_thread.join().ok();
+    let stderr_tail = stderr_thread.join().unwrap_or_default();
+
+    if job.as_ref().is_some_and(|job| job.is_cancelled()) {
+        Err(jobs::CANCELLED.to_string())
+    } else if status.success() {
+        if let Some(job) = &job {
+            job.record_completed(command_line);
+        }
+        Ok(())
+    } else if let Some(code) = status.code() {
+        Err(format!("{} failed with exit code {}{}", what, code, tail_suffix(&stderr_tail)))
+    } else {
+        Err(format!("{} terminated by signal{}", what, tail_suffix(&stderr_tail)))
+    }
+}
+
+/// Emit each line read from `reader` on a new thread
+///
+/// The thread returns the last [`STDERR_TAIL_LINES`] lines it read.
+fn stream_lines<R>(reader: R, stream: OutputStream, emit: &EventSink) -> JoinHandle<VecDeque<String>>
+where
+    R: Read + Send + 'static,
+{
+    let emit = emit.clone();
+    std::thread::spawn(move || {
+        let mut tail = VecDeque::with_capacity(STDERR_TAIL_LINES);
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if tail.len() == STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line.clone());
+            emit(InstallEvent::output(line, stream));
+        }
+        tail
+    })
+}
+
+fn tail_suffix(tail: &VecDeque<String>) -> String {
+    if tail.is_empty() {
+        String::new()
+    } else {
+        format!(":\n{}", Vec::from(tail.clone()).join("\n"))
+    }
+}
+
+/// Render a command as a single line, e.g. "brew install node"
+fn command_line(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Build a command from a command line such as `["npm", "install", "-g", "x"]`
+///
+/// # Returns
+/// * `Ok(Command)` running the first element with the rest as arguments
+/// * `Err(String)` if the command line is empty
+pub fn command_from(args: &[String]) -> Result<Command, String> {
+    let (program, rest) = args
+        .split_first()
+        .filter(|(program, _)| !program.is_empty())
+        .ok_or("Cannot run an empty command")?;
+    let mut cmd = Command::new(program);
+    cmd.args(rest);
+    Ok(cmd)
+}
+
+/// Check a package name from a template before it goes on a command line
+///
+/// Names are passed as a single argument, often to a command running as root,
+/// so a leading `-` would turn them into an option such as
+/// `-oDPkg::Pre-Invoke::=<cmd>`. Only `[A-Za-z0-9._+:@/-]` is allowed.
+pub fn validate_package_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Package name cannot be empty".to_string());
+    }
+    if name.starts_with('-') {
+        return Err(format!("Package name '{}' cannot start with '-'", name));
+    }
+    let allowed = |c: char| c.is_ascii_alphanumeric() || "._+:@/-".contains(c);
+    if !name.chars().all(allowed) {
+        return Err(format!("Package name '{}' contains characters that aren't allowed", name));
+    }
+    Ok(())
+}
+
+/// Proxy environment variables for child processes, if a proxy is configured
+pub fn proxy_env_vars() -> Vec<(String, String)> {
+    let mut env_vars: Vec<(String, String)> = Vec::new();
+    if let Ok(settings) = crate::settings::storage::load_settings() {
+        if let Some(ref proxy) = settings.proxy {
+            if let Some((env_name, env_value)) = crate::settings::proxy::get_proxy_env_var(proxy) {
+                env_vars.push((env_name, env_value));
+            }
+        }
+    }
+    env_vars
+}
+
+/// Run an async operation to completion from synchronous installer code
+///
+/// The future runs on a fresh single-threaded runtime in its own thread, so
+/// this also works when called from inside an async command. The calling
+/// thread's job is carried over, so the future can check for cancellation.
+pub fn block_on<F>(future: F) -> Result<F::Output, String>
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    let job = jobs::current();
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| format!("Failed to start async runtime: {}", e))?;
+                Ok(match &job {
+                    Some(job) => jobs::run_in_job(job, || runtime.block_on(future)),
+                    None => runtime.block_on(future),
+                })
+            })
+            .join()
+            .map_err(|_| "Async task panicked".to_string())?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(method_type: &str) -> InstallMethod {
+        InstallMethod {
+            method_type: method_type.to_string(),
+            brew_cask_name: None,
+            brew_formula_name: None,
+            brew_tap: None,
+            dmg_url: None,
+            dmg_install_steps: None,
+            script_commands: None,
+            github_repo: None,
+            github_asset_pattern: None,
+            system_package: None,
+            system_packages: None,
+            package_name: None,
+            package_version: None,
+            runtime_name: None,
+            runtime_manager: None,
+            uninstall_commands: None,
+            sha256: None,
+            checksum_url: None,
+            checksum_pattern: None,
+            binary_path: None,
+        }
+    }
+
+    fn template_with(methods: Vec<InstallMethod>) -> CliToolTemplate {
+        CliToolTemplate {
+            id: "tool".to_string(),
+            name: "Tool".to_string(),
+            executable: "tool".to_string(),
+            version_command: "--version".to_string(),
+            version_parser: "stdout".to_string(),
+            config_files: vec![],
+            install_methods: Some(methods),
+            dependencies: None,
+            category: None,
+            emoji: None,
+        }
+    }
+
+    #[test]
+    fn test_installer_for_dispatches_on_method_type() {
+        let mut tap = method("brew");
+        tap.brew_tap = Some("acme/tap".to_string());
+        tap.brew_formula_name = Some("tool".to_string());
+        let mut script = method("script");
+        script.script_commands = Some(vec!["npm install -g tool".to_string()]);
+        let template = template_with(vec![tap, script]);
+
+        let brew = installer_for(&template, 0).unwrap();
+        assert_eq!(brew.plan(), vec!["brew tap acme/tap", "brew install tool"]);
+
+        let script = installer_for(&template, 1).unwrap();
+        assert_eq!(script.plan(), vec!["npm install -g tool"]);
+    }
+
+    #[test]
+    fn test_installer_for_rejects_bad_methods() {
+        let template = template_with(vec![method("brew"), method("carrier-pigeon"), method("npm")]);
+
+        assert!(installer_for(&template, 0).err().unwrap().contains("no package name"));
+        assert!(installer_for(&template, 1).err().unwrap().contains("not supported"));
+        assert!(installer_for(&template, 2).err().unwrap().contains("npm install method"));
+        assert!(installer_for(&template, 3).err().unwrap().contains("no install method #3"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_streamed_captures_both_streams() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink_events = Arc::clone(&events);
+        let emit: EventSink = Arc::new(move |event| sink_events.lock().unwrap().push(event));
+
+        // Enough stderr to fill the pipe buffer if it weren't drained
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo out; i=0; while [ $i -lt 5000 ]; do echo err $i >&2; i=$((i+1)); done; exit 3"]);
+        let error = run_streamed(cmd, "script", &emit).unwrap_err();
+
+        assert!(error.starts_with("script failed with exit code 3:\nerr 4980\n"));
+        assert!(error.ends_with("err 4999"));
+        let events = events.lock().unwrap();
+        let streams: Vec<OutputStream> = events
+            .iter()
+            .filter_map(|event| match event {
+                InstallEvent::Output { stream, .. } => Some(*stream),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(streams.len(), 5001);
+        assert_eq!(streams.iter().filter(|s| **s == OutputStream::Stdout).count(), 1);
+    }
+
+    #[test]
+    fn test_only_installed_dependents_block_uninstall() {
+        let mut node = template_with(vec![]);
+        node.id = "node".to_string();
+        let mut codex = template_with(vec![]);
+        codex.id = "codex".to_string();
+        codex.name = "Codex".to_string();
+        codex.dependencies = Some(vec!["node >= 18".to_string()]);
+        let mut gemini = template_with(vec![]);
+        gemini.id = "gemini".to_string();
+        gemini.name = "Gemini".to_string();
+        gemini.dependencies = Some(vec!["bun | node".to_string()]);
+        let templates = vec![node, codex, gemini];
+
+        let installed: HashSet<String> = ["node", "gemini"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(installed_dependents("node", &templates, &installed), vec!["Gemini"]);
+        assert!(installed_dependents("node", &templates, &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_purge_only_removes_files_in_home() {
+        let home = std::env::temp_dir().join(format!("purge-home-{}", std::process::id()));
+        std::fs::create_dir_all(home.join(".config/tool")).unwrap();
+        std::fs::write(home.join(".config/tool/config.toml"), "").unwrap();
+
+        assert!(check_purgeable(&home.join(".config/tool/config.toml"), &home).is_ok());
+        assert!(check_purgeable(&home.join(".config/tool/missing.toml"), &home).is_ok());
+        assert!(check_purgeable(&home.join(".config/tool"), &home).unwrap_err().contains("directory"));
+        assert!(check_purgeable(&home, &home).is_err());
+        assert!(check_purgeable(Path::new("/"), &home).is_err());
+        assert!(check_purgeable(&home.join("../other/file"), &home).unwrap_err().contains(".."));
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[test]
+    fn test_event_payload_format() {
+        let status = InstallEvent::Status { message: "Installing...".to_string() };
+        let progress = InstallEvent::Progress { downloaded: 5, total: 10, resumed_from: 2 };
+
+        assert_eq!(
+            status.to_payload(),
+            serde_json::json!({ "type": "status", "message": "Installing..." })
+        );
+        assert_eq!(
+            progress.to_payload(),
+            serde_json::json!({ "type": "progress", "downloaded": 5, "total": 10, "resumedFrom": 2 })
+        );
+    }
+
+    #[test]
+    fn test_command_from_rejects_empty_command_lines() {
+        assert!(command_from(&[]).is_err());
+        assert!(command_from(&["".to_string()]).is_err());
+
+        let cmd = command_from(&["npm".to_string(), "install".to_string()]).unwrap();
+        assert_eq!(cmd.get_program(), "npm");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["install"]);
+    }
+}
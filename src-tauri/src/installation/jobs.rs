@@ -0,0 +1,293 @@
+use crate::installation::history::{self, JobRecorder};
+use crate::installation::installer::{EventSink, InstallEvent};
+use crate::types::InstallJobStatus;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Error returned by operations that stopped because their job was cancelled
+pub const CANCELLED: &str = "Installation cancelled";
+
+/// How long a cancelled process group gets to exit before it is killed
+const KILL_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// A running install that can be cancelled
+///
+/// Every command started through `installer::run_streamed` while the job is
+/// current runs in its own process group, so cancelling also stops whatever
+/// the command spawned, e.g. the `sh` behind a `curl | sh` script.
+#[derive(Debug, Default)]
+pub struct Job {
+    pub id: String,
+    cancelled: AtomicBool,
+    /// Process (group) IDs of the commands currently running
+    processes: Mutex<Vec<u32>>,
+    /// Command lines in the order they were started
+    started: Mutex<Vec<String>>,
+    /// Command lines that finished successfully, in order
+    completed: Mutex<Vec<String>>,
+    /// Where the job is recorded in the install history
+    recorder: OnceLock<Arc<JobRecorder>>,
+}
+
+impl Job {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Commands started so far
+    pub fn started_commands(&self) -> Vec<String> {
+        lock(&self.started).clone()
+    }
+
+    pub fn record_started(&self, command: String) {
+        lock(&self.started).push(command);
+    }
+
+    /// Commands that completed before now
+    pub fn completed_commands(&self) -> Vec<String> {
+        lock(&self.completed).clone()
+    }
+
+    pub fn record_completed(&self, command: String) {
+        lock(&self.completed).push(command);
+    }
+
+    /// Write an event of one of the job's tools to the job's output log
+    pub fn log_tool_event(&self, tool_id: &str, event: &InstallEvent) {
+        if let Some(recorder) = self.recorder.get() {
+            recorder.log(Some(tool_id), event);
+        }
+    }
+
+    /// Track a spawned process, killing it right away if the job was cancelled
+    pub fn register_process(&self, pid: u32) {
+        lock(&self.processes).push(pid);
+        if self.is_cancelled() {
+            terminate_process_group(pid);
+        }
+    }
+
+    pub fn unregister_process(&self, pid: u32) {
+        lock(&self.processes).retain(|p| *p != pid);
+    }
+
+    fn is_running(&self, pid: u32) -> bool {
+        lock(&self.processes).contains(&pid)
+    }
+
+    /// Mark the job cancelled and stop every process it is running
+    fn cancel(self: &Arc<Self>) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        let pids = lock(&self.processes).clone();
+        for pid in pids {
+            terminate_process_group(pid);
+
+            let job = Arc::clone(self);
+            std::thread::spawn(move || {
+                std::thread::sleep(KILL_GRACE_PERIOD);
+                if job.is_running(pid) {
+                    kill_process_group(pid);
+                }
+            });
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<Job>>> = const { RefCell::new(None) };
+}
+
+fn registry() -> &'static Mutex<HashMap<String, Arc<Job>>> {
+    static JOBS: OnceLock<Mutex<HashMap<String, Arc<Job>>>> = OnceLock::new();
+    JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Register a new job with a unique ID
+pub fn start_job() -> Arc<Job> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let id = format!(
+        "job-{}-{}",
+        chrono::Utc::now().timestamp_millis(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    );
+    let job = Arc::new(Job {
+        id: id.clone(),
+        ..Job::default()
+    });
+    lock(registry()).insert(id, Arc::clone(&job));
+    job
+}
+
+/// Remove a job from the registry once it has finished
+pub fn finish_job(job_id: &str) {
+    lock(registry()).remove(job_id);
+}
+
+/// Whether a job with this ID is registered, i.e. still running
+pub fn is_running(job_id: &str) -> bool {
+    lock(registry()).contains_key(job_id)
+}
+
+/// Run an install as a new job and record it in the install history
+///
+/// The job ID is announced through a `started` event before `f` runs, and the
+/// job is unregistered once `f` returns. `f` receives a sink that also writes
+/// every event to the job's output log; jobs installing several tools can log
+/// per-tool events through [`Job::log_tool_event`] instead.
+///
+/// # Arguments
+/// * `emit` - Sink forwarding events to the frontend
+/// * `tool` - Tool or package being installed
+/// * `method` - Install method, e.g. "brew"
+/// * `f` - Runs the install
+pub fn run_job<T>(
+    emit: &EventSink,
+    tool: Option<&str>,
+    method: Option<&str>,
+    f: impl FnOnce(&Arc<Job>, &EventSink) -> Result<T, String>,
+) -> Result<T, String> {
+    let job = start_job();
+    let recorder = Arc::new(JobRecorder::start(
+        &job.id,
+        tool.map(|t| t.to_string()),
+        method.map(|m| m.to_string()),
+    ));
+
+    let _ = job.recorder.set(Arc::clone(&recorder));
+
+    let forward = emit.clone();
+    let log = Arc::clone(&recorder);
+    let emit: EventSink = Arc::new(move |event: InstallEvent| {
+        log.log(None, &event);
+        forward(event);
+    });
+    emit(InstallEvent::Started {
+        job_id: job.id.clone(),
+    });
+
+    let result = run_in_job(&job, || f(&job, &emit));
+
+    let status = match &result {
+        Ok(_) => InstallJobStatus::Succeeded,
+        Err(_) if job.is_cancelled() => InstallJobStatus::Cancelled,
+        Err(_) => InstallJobStatus::Failed,
+    };
+    recorder.finish(status, job.started_commands(), result.as_ref().err().cloned());
+    finish_job(&job.id);
+
+    let retention = crate::settings::storage::load_settings()
+        .ok()
+        .and_then(|settings| settings.install_history);
+    let _ = history::prune_install_history(retention.as_ref());
+
+    result
+}
+
+/// Run `f` with `job` as the current job of this thread
+pub fn run_in_job<T>(job: &Arc<Job>, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(Arc::clone(job))));
+    let result = f();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// The job of the calling thread, if any
+pub fn current() -> Option<Arc<Job>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Cancel a running job
+///
+/// # Returns
+/// * `Ok(Vec<String>)` with the commands that had completed
+/// * `Err(String)` if no job with that ID is running
+pub fn cancel_job(job_id: &str) -> Result<Vec<String>, String> {
+    let job = lock(registry())
+        .get(job_id)
+        .cloned()
+        .ok_or_else(|| format!("No running install with job ID {}", job_id))?;
+    job.cancel();
+    Ok(job.completed_commands())
+}
+
+/// Put a command into its own process group so it can be stopped as a whole
+pub fn isolate_process_group(cmd: &mut std::process::Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = cmd;
+}
+
+#[cfg(unix)]
+fn signal_process_group(pid: u32, signal: libc::c_int) {
+    // The group ID equals the PID of its leader; a negative PID addresses the group
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), signal);
+    }
+}
+
+fn terminate_process_group(pid: u32) {
+    #[cfg(unix)]
+    signal_process_group(pid, libc::SIGTERM);
+    #[cfg(not(unix))]
+    kill_process_group(pid);
+}
+
+fn kill_process_group(pid: u32) {
+    #[cfg(unix)]
+    signal_process_group(pid, libc::SIGKILL);
+    #[cfg(not(unix))]
+    {
+        let _ = std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .output();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installation::installer::run_streamed;
+    use std::process::Command;
+    use std::time::Instant;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cancel_stops_the_whole_process_group() {
+        let job = start_job();
+        let emit: EventSink = Arc::new(|_: InstallEvent| {});
+
+        let worker_job = Arc::clone(&job);
+        let worker = std::thread::spawn(move || {
+            run_in_job(&worker_job, || {
+                run_streamed(Command::new("true"), "true", &emit)?;
+                // The grandchild `sleep` keeps stdout open unless it is killed too
+                let mut cmd = Command::new("sh");
+                cmd.args(["-c", "sleep 30 & sleep 30"]);
+                run_streamed(cmd, "sleep", &emit)
+            })
+        });
+
+        while lock(&job.processes).is_empty() || job.completed_commands().is_empty() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let started = Instant::now();
+        let completed = cancel_job(&job.id).unwrap();
+
+        assert_eq!(worker.join().unwrap(), Err(CANCELLED.to_string()));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(completed, vec!["true"]);
+        finish_job(&job.id);
+        assert!(cancel_job(&job.id).is_err());
+    }
+}
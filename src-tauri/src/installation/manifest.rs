@@ -0,0 +1,157 @@
+use crate::types::ManifestEntry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Serializes every load-modify-save of the manifest, since parallel
+/// installs record their files at the same time
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_manifest() -> MutexGuard<'static, ()> {
+    MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Get the path of the manifest of files placed by the app
+fn get_manifest_path() -> Result<PathBuf, String> {
+    let mut dir = dirs::data_dir()
+        .ok_or("Failed to find data directory")?;
+
+    dir.push("dev-env-helper");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+
+    Ok(dir.join("install-manifest.json"))
+}
+
+fn load_from(path: &Path) -> Result<HashMap<String, ManifestEntry>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read install manifest {}: {}", path.display(), e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse install manifest {}: {}", path.display(), e))
+}
+
+/// Write the manifest to a temporary file and rename it into place, so a
+/// crash never leaves a truncated manifest behind
+fn save_to(path: &Path, entries: &HashMap<String, ManifestEntry>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize install manifest: {}", e))?;
+
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write install manifest {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path)
+        .map_err(|e| format!("Failed to write install manifest {}: {}", path.display(), e))
+}
+
+/// Record the files the app placed while installing a tool
+///
+/// Files from an earlier install of the same tool are kept, so an upgrade
+/// that places different files still removes everything on uninstall.
+pub fn record_files(tool_id: &str, method: &str, files: &[PathBuf]) -> Result<(), String> {
+    record_files_in(&get_manifest_path()?, tool_id, method, files)
+}
+
+fn record_files_in(path: &Path, tool_id: &str, method: &str, files: &[PathBuf]) -> Result<(), String> {
+    let _guard = lock_manifest();
+    let mut entries = load_from(path)?;
+    let entry = entries.entry(tool_id.to_string()).or_insert_with(|| ManifestEntry {
+        method: method.to_string(),
+        files: Vec::new(),
+        installed_at: String::new(),
+    });
+
+    entry.method = method.to_string();
+    entry.installed_at = chrono::Utc::now().to_rfc3339();
+    for file in files {
+        let file = file.to_string_lossy().to_string();
+        if !entry.files.contains(&file) {
+            entry.files.push(file);
+        }
+    }
+
+    save_to(path, &entries)
+}
+
+/// Get the manifest entry of a tool, if the app placed files for it
+pub fn get_entry(tool_id: &str) -> Result<Option<ManifestEntry>, String> {
+    let path = get_manifest_path()?;
+    let _guard = lock_manifest();
+    Ok(load_from(&path)?.remove(tool_id))
+}
+
+/// Forget the files of a tool after they were removed
+pub fn remove_entry(tool_id: &str) -> Result<(), String> {
+    remove_entry_in(&get_manifest_path()?, tool_id)
+}
+
+fn remove_entry_in(path: &Path, tool_id: &str) -> Result<(), String> {
+    let _guard = lock_manifest();
+    let mut entries = load_from(path)?;
+    if entries.remove(tool_id).is_some() {
+        save_to(path, &entries)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_remove_entries() {
+        let path = std::env::temp_dir().join(format!("install-manifest-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        record_files_in(&path, "rg", "github", &[PathBuf::from("/home/me/.local/bin/rg")]).unwrap();
+        record_files_in(
+            &path,
+            "rg",
+            "github",
+            &[PathBuf::from("/home/me/.local/bin/rg"), PathBuf::from("/home/me/.local/share/man/rg.1")],
+        )
+        .unwrap();
+        record_files_in(&path, "fd", "github", &[PathBuf::from("/home/me/.local/bin/fd")]).unwrap();
+
+        let entries = load_from(&path).unwrap();
+        assert_eq!(entries["rg"].files, vec!["/home/me/.local/bin/rg", "/home/me/.local/share/man/rg.1"]);
+
+        remove_entry_in(&path, "rg").unwrap();
+        let entries = load_from(&path).unwrap();
+        assert!(!entries.contains_key("rg"));
+        assert!(entries.contains_key("fd"));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parallel_records_keep_both_entries() {
+        let path = std::env::temp_dir().join(format!("install-manifest-parallel-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        std::thread::scope(|scope| {
+            for tool in ["rg", "fd"] {
+                let path = &path;
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        let file = PathBuf::from(format!("/home/me/.local/bin/{}", tool));
+                        record_files_in(path, tool, "github", &[file]).unwrap();
+                    }
+                });
+            }
+        });
+
+        let entries = load_from(&path).unwrap();
+        assert!(entries.contains_key("rg") && entries.contains_key("fd"));
+        assert!(!path.with_extension("json.tmp").exists());
+        fs::remove_file(&path).ok();
+    }
+}
@@ -1,3 +1,16 @@
+pub mod archive;
 pub mod brew;
+pub mod checksum;
 pub mod download;
+pub mod dry_run;
+pub mod ecosystem;
+pub mod executor;
+pub mod history;
+pub mod installer;
+pub mod jobs;
+pub mod manifest;
+pub mod runtime_manager;
 pub mod script;
+pub mod script_safety;
+pub mod system_package;
+pub mod updates;
@@ -0,0 +1,449 @@
+use crate::cli::detection;
+use crate::installation::installer::{
+    command_from, finish, proxy_env_vars, run_streamed, EventSink, InstallEvent, Installer,
+};
+use crate::types::RuntimeVersions;
+use std::path::Path;
+use std::process::Command;
+
+/// A tool version manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeManager {
+    Mise,
+    Asdf,
+}
+
+impl RuntimeManager {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuntimeManager::Mise => "mise",
+            RuntimeManager::Asdf => "asdf",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mise" => Some(RuntimeManager::Mise),
+            "asdf" => Some(RuntimeManager::Asdf),
+            _ => None,
+        }
+    }
+
+    /// Find an installed version manager, preferring mise
+    pub fn detect() -> Option<Self> {
+        [RuntimeManager::Mise, RuntimeManager::Asdf]
+            .into_iter()
+            .find(|manager| detection::which(manager.as_str()).is_some())
+    }
+
+    /// Name of a runtime in this manager
+    ///
+    /// asdf plugins don't always use the tool's own name, e.g. node is "nodejs".
+    pub fn tool_name<'a>(&self, runtime: &'a str) -> &'a str {
+        match (self, runtime) {
+            (RuntimeManager::Asdf, "node") => "nodejs",
+            (RuntimeManager::Asdf, "go") => "golang",
+            _ => runtime,
+        }
+    }
+}
+
+/// Installs and activates a runtime version through mise or asdf
+#[derive(Debug, Clone)]
+pub struct RuntimeInstaller {
+    pub manager: RuntimeManager,
+    /// Runtime name as used by mise, e.g. "node"
+    pub runtime: String,
+    /// Version to install, "latest" when `None`
+    pub version: Option<String>,
+    /// Whether asdf is 0.16 or newer, which replaced `asdf global` with `asdf set`
+    ///
+    /// `None` until the commands are run, so building an installer (e.g. for a
+    /// dry run) never spawns asdf.
+    pub asdf_set: Option<bool>,
+}
+
+impl RuntimeInstaller {
+    pub fn new(manager: RuntimeManager, runtime: &str, version: Option<&str>) -> Self {
+        Self {
+            manager,
+            runtime: runtime.to_string(),
+            version: version.map(|v| v.to_string()),
+            asdf_set: None,
+        }
+    }
+
+    /// Whether to activate versions with `asdf set`, asking asdf if not known yet
+    fn uses_asdf_set(&self) -> bool {
+        self.manager == RuntimeManager::Asdf && self.asdf_set.unwrap_or_else(asdf_supports_set)
+    }
+
+    fn tool(&self) -> &str {
+        self.manager.tool_name(&self.runtime)
+    }
+
+    fn version(&self) -> &str {
+        self.version.as_deref().unwrap_or("latest")
+    }
+
+    /// Command lines installing the version and making it the global default
+    pub fn install_commands(&self) -> Vec<Vec<String>> {
+        self.commands_for(self.version(), self.uses_asdf_set())
+    }
+
+    /// Command lines installing the latest version and making it the global default
+    pub fn upgrade_commands(&self) -> Vec<Vec<String>> {
+        self.commands_for("latest", self.uses_asdf_set())
+    }
+
+    fn commands_for(&self, version: &str, asdf_set: bool) -> Vec<Vec<String>> {
+        let tool = self.tool();
+        match self.manager {
+            RuntimeManager::Mise => vec![
+                args(&["mise", "install", &format!("{}@{}", tool, version)]),
+                args(&["mise", "use", "--global", &format!("{}@{}", tool, version)]),
+            ],
+            RuntimeManager::Asdf => {
+                let activate = if asdf_set {
+                    args(&["asdf", "set", "--home", tool, version])
+                } else {
+                    args(&["asdf", "global", tool, version])
+                };
+                vec![args(&["asdf", "install", tool, version]), activate]
+            }
+        }
+    }
+
+    /// Command line removing the configured version
+    pub fn uninstall_command(&self) -> Option<Vec<String>> {
+        let version = self.version.as_deref()?;
+        let tool = self.tool();
+        Some(match self.manager {
+            RuntimeManager::Mise => args(&["mise", "uninstall", &format!("{}@{}", tool, version)]),
+            RuntimeManager::Asdf => args(&["asdf", "uninstall", tool, version]),
+        })
+    }
+
+    /// asdf needs the plugin of a runtime before it can install it
+    fn ensure_asdf_plugin(&self, emit: &EventSink) -> Result<(), String> {
+        let output = Command::new("asdf")
+            .args(["plugin", "list"])
+            .output()
+            .map_err(|e| format!("Failed to run asdf plugin list: {}", e))?;
+        let installed = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|line| line.trim() == self.tool());
+        if installed {
+            return Ok(());
+        }
+
+        emit(InstallEvent::Status {
+            message: format!("Adding asdf plugin {}...", self.tool()),
+        });
+        let command = args(&["asdf", "plugin", "add", self.tool()]);
+        run_streamed(command_from(&command)?, &command.join(" "), emit)
+    }
+
+    fn run_commands(&self, commands: Vec<Vec<String>>, emit: &EventSink) -> Result<(), String> {
+        if detection::which(self.manager.as_str()).is_none() {
+            return Err(format!("{} is not installed", self.manager.as_str()));
+        }
+        if self.manager == RuntimeManager::Asdf {
+            self.ensure_asdf_plugin(emit)?;
+        }
+        for command in commands {
+            let mut cmd = command_from(&command)?;
+            cmd.envs(proxy_env_vars());
+            run_streamed(cmd, &command.join(" "), emit)?;
+        }
+        Ok(())
+    }
+}
+
+impl Installer for RuntimeInstaller {
+    fn plan(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        if self.manager != RuntimeManager::Asdf || self.asdf_set.is_some() {
+            if self.manager == RuntimeManager::Asdf {
+                steps.push(format!("asdf plugin add {} (if missing)", self.tool()));
+            }
+            steps.extend(self.install_commands().iter().map(|c| c.join(" ")));
+            return steps;
+        }
+
+        // The asdf version is only checked when installing
+        let set = self.commands_for(self.version(), true);
+        let global = self.commands_for(self.version(), false);
+        steps.push(format!("asdf plugin add {} (if missing)", self.tool()));
+        steps.push(set[0].join(" "));
+        steps.push(format!(
+            "{} (asdf 0.16 or newer, otherwise {})",
+            set[1].join(" "),
+            global[1].join(" ")
+        ));
+        steps
+    }
+
+    fn environment(&self) -> Vec<(String, String)> {
+        proxy_env_vars()
+    }
+
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        emit(InstallEvent::Status {
+            message: format!("Installing {} {} via {}...", self.runtime, self.version(), self.manager.as_str()),
+        });
+        let result = self.run_commands(self.install_commands(), emit);
+        finish(emit, result, format!("Installed and activated {} {}", self.runtime, self.version()))
+    }
+
+    fn verify(&self) -> Result<bool, String> {
+        let installed = list_installed_versions(self.manager, &self.runtime)?;
+        Ok(match self.version.as_deref() {
+            Some(version) => installed.iter().any(|v| v == version),
+            None => !installed.is_empty(),
+        })
+    }
+
+    fn upgrade(&self, emit: &EventSink) -> Result<(), String> {
+        emit(InstallEvent::Status {
+            message: format!("Upgrading {} via {}...", self.runtime, self.manager.as_str()),
+        });
+        let result = self.run_commands(self.upgrade_commands(), emit);
+        finish(emit, result, format!("Upgraded {} to the latest version", self.runtime))
+    }
+
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String> {
+        let command = self.uninstall_command().ok_or_else(|| {
+            format!("Set a version to uninstall {} through {}", self.runtime, self.manager.as_str())
+        })?;
+        emit(InstallEvent::Status {
+            message: format!("Uninstalling {} {}...", self.runtime, self.version()),
+        });
+        let result = command_from(&command).and_then(|cmd| run_streamed(cmd, &command.join(" "), emit));
+        finish(emit, result, format!("Successfully uninstalled {} {}", self.runtime, self.version()))
+    }
+}
+
+fn args(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|p| p.to_string()).collect()
+}
+
+/// Check whether the installed asdf has `asdf set` (0.16 and newer)
+fn asdf_supports_set() -> bool {
+    Command::new("asdf")
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| parse_asdf_supports_set(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or(false)
+}
+
+/// Parse `asdf --version` output, e.g. "asdf version 0.16.0 (revision 1a2b3c)" or "v0.14.0-ccdd47d"
+fn parse_asdf_supports_set(output: &str) -> bool {
+    let Some(version) = output
+        .split_whitespace()
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+    else {
+        return false;
+    };
+    let mut parts = version.split(['.', '-']);
+    let major: u64 = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    let minor: u64 = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    major > 0 || minor >= 16
+}
+
+/// List the installed versions of a runtime
+pub fn list_installed_versions(manager: RuntimeManager, runtime: &str) -> Result<Vec<String>, String> {
+    let tool = manager.tool_name(runtime);
+    let output = match manager {
+        RuntimeManager::Mise => Command::new("mise").args(["ls", "--installed", "--json", tool]).output(),
+        RuntimeManager::Asdf => Command::new("asdf").args(["list", tool]).output(),
+    }
+    .map_err(|e| format!("Failed to list {} versions: {}", runtime, e))?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(match manager {
+        RuntimeManager::Mise => parse_mise_ls(&stdout),
+        RuntimeManager::Asdf => parse_asdf_list(&stdout),
+    })
+}
+
+/// Detect the version of a runtime that is active in a directory
+///
+/// Both managers resolve `.tool-versions` (and mise also `mise.toml`) from
+/// the directory upwards, falling back to the global version.
+pub fn active_version(manager: RuntimeManager, runtime: &str, directory: &Path) -> Result<Option<String>, String> {
+    let tool = manager.tool_name(runtime);
+    let output = Command::new(manager.as_str())
+        .args(["current", tool])
+        .current_dir(directory)
+        .output()
+        .map_err(|e| format!("Failed to run {} current: {}", manager.as_str(), e))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(match manager {
+        RuntimeManager::Mise => parse_mise_current(&stdout),
+        RuntimeManager::Asdf => parse_asdf_current(&stdout, tool),
+    })
+}
+
+/// Installed versions and the version active in `directory`
+pub fn runtime_versions(runtime: &str, directory: Option<&Path>) -> Result<RuntimeVersions, String> {
+    let manager = RuntimeManager::detect().ok_or("Neither mise nor asdf is installed")?;
+    let installed = list_installed_versions(manager, runtime)?;
+    let active = match directory {
+        Some(dir) => active_version(manager, runtime, dir)?,
+        None => None,
+    };
+
+    Ok(RuntimeVersions {
+        manager: manager.as_str().to_string(),
+        runtime: runtime.to_string(),
+        installed,
+        active,
+    })
+}
+
+/// Parse `mise ls --installed --json <tool>`, an array of `{"version": ...}` objects
+pub fn parse_mise_ls(output: &str) -> Vec<String> {
+    serde_json::from_str::<Vec<serde_json::Value>>(output)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry["version"].as_str().map(|v| v.to_string()))
+        .collect()
+}
+
+/// Parse `asdf list <plugin>`, one version per line with `*` marking the current one
+pub fn parse_asdf_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("No versions"))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Parse `mise current <tool>`, which prints the active versions separated by spaces
+pub fn parse_mise_current(output: &str) -> Option<String> {
+    output.split_whitespace().next().map(|v| v.to_string())
+}
+
+/// Parse `asdf current <plugin>`
+///
+/// Older releases print `nodejs  20.11.1  /home/me/.tool-versions`, 0.16 and
+/// newer add a header line and an "Installed" column.
+pub fn parse_asdf_current(output: &str, tool: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? != tool {
+            return None;
+        }
+        let version = parts.next()?;
+        let is_version = version.chars().next().is_some_and(|c| c.is_ascii_digit())
+            || version == "system";
+        is_version.then(|| version.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installer(manager: RuntimeManager, runtime: &str, version: Option<&str>, asdf_set: bool) -> RuntimeInstaller {
+        RuntimeInstaller {
+            manager,
+            runtime: runtime.to_string(),
+            version: version.map(|v| v.to_string()),
+            asdf_set: Some(asdf_set),
+        }
+    }
+
+    fn joined(commands: Vec<Vec<String>>) -> Vec<String> {
+        commands.iter().map(|c| c.join(" ")).collect()
+    }
+
+    #[test]
+    fn test_mise_commands() {
+        let node = installer(RuntimeManager::Mise, "node", Some("20.11.1"), false);
+
+        assert_eq!(
+            joined(node.install_commands()),
+            vec!["mise install node@20.11.1", "mise use --global node@20.11.1"]
+        );
+        assert_eq!(node.uninstall_command().unwrap().join(" "), "mise uninstall node@20.11.1");
+        assert_eq!(
+            joined(node.upgrade_commands()),
+            vec!["mise install node@latest", "mise use --global node@latest"]
+        );
+    }
+
+    #[test]
+    fn test_asdf_commands_use_plugin_names() {
+        let old = installer(RuntimeManager::Asdf, "node", None, false);
+        assert_eq!(
+            joined(old.install_commands()),
+            vec!["asdf install nodejs latest", "asdf global nodejs latest"]
+        );
+        assert!(old.uninstall_command().is_none());
+
+        let new = installer(RuntimeManager::Asdf, "python", Some("3.12.4"), true);
+        assert_eq!(
+            joined(new.install_commands()),
+            vec!["asdf install python 3.12.4", "asdf set --home python 3.12.4"]
+        );
+    }
+
+    #[test]
+    fn test_asdf_plan_shows_both_activation_commands() {
+        let node = RuntimeInstaller::new(RuntimeManager::Asdf, "node", Some("20.11.1"));
+        assert_eq!(
+            node.plan(),
+            vec![
+                "asdf plugin add nodejs (if missing)",
+                "asdf install nodejs 20.11.1",
+                "asdf set --home nodejs 20.11.1 (asdf 0.16 or newer, otherwise asdf global nodejs 20.11.1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_asdf_version() {
+        assert!(parse_asdf_supports_set("asdf version 0.16.2 (revision 1a2b3c)\n"));
+        assert!(parse_asdf_supports_set("asdf version v0.18.0\n"));
+        assert!(!parse_asdf_supports_set("v0.14.0-ccdd47d\n"));
+        assert!(!parse_asdf_supports_set(""));
+    }
+
+    #[test]
+    fn test_parse_version_lists() {
+        let mise = r#"[{"version":"20.11.1","install_path":"/x","active":true},{"version":"22.3.0"}]"#;
+        assert_eq!(parse_mise_ls(mise), vec!["20.11.1", "22.3.0"]);
+
+        assert_eq!(parse_asdf_list("  18.19.0\n *20.11.1\n"), vec!["18.19.0", "20.11.1"]);
+        assert!(parse_asdf_list("  No versions installed\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_current_version() {
+        assert_eq!(parse_mise_current("20.11.1\n").as_deref(), Some("20.11.1"));
+        assert_eq!(parse_mise_current("\n"), None);
+
+        let old = "nodejs          20.11.1         /home/me/project/.tool-versions\n";
+        assert_eq!(parse_asdf_current(old, "nodejs").as_deref(), Some("20.11.1"));
+
+        let new = "Name            Version         Source                        Installed\nnodejs          22.3.0          /home/me/.tool-versions       true\n";
+        assert_eq!(parse_asdf_current(new, "nodejs").as_deref(), Some("22.3.0"));
+
+        let unset = "nodejs          ______          No version is set. Run \"asdf <global|shell|local> nodejs <version>\"\n";
+        assert_eq!(parse_asdf_current(unset, "nodejs"), None);
+    }
+}
@@ -1,38 +1,72 @@
-use std::process::{Command, Stdio};
-use std::thread;
-
-/// Events emitted during script installation
-#[derive(Debug, Clone)]
-pub enum ScriptInstallEvent {
-    Status { message: String },
-    Output { line: String },
-    Success { message: String },
-    Error { message: String },
-}
+use crate::cli::{detection, template};
+use crate::installation::installer::{finish, run_streamed, EventSink, InstallEvent, Installer};
+use crate::installation::script_safety;
+use crate::types::InstallMethod;
+use std::process::Command;
 
 /// Execute a script installation
 ///
 /// # Arguments
 /// * `commands` - Vector of shell commands to execute in sequence
-/// * `emit_event` - Callback to emit progress events to the frontend
+/// * `emit` - Sink receiving progress events for the frontend
 ///
 /// # Returns
 /// * `Ok(())` on successful installation
 /// * `Err(String)` with error message on failure
-pub fn execute_script_install<F>(
-    commands: &[String],
-    mut emit_event: F,
-) -> Result<(), String>
-where
-    F: FnMut(ScriptInstallEvent) + Clone + Send + 'static,
-{
+pub fn execute_script_install(commands: &[String], emit: &EventSink) -> Result<(), String> {
+    let result = run_commands(commands, emit);
+    finish(emit, result, "Script installation completed successfully".to_string())
+}
+
+/// Find a template's script install method
+///
+/// Without a `method_index`, the template's first script method is used.
+/// Scripts only ever come from templates, never from the frontend.
+fn template_script_method(template_id: &str, method_index: Option<usize>) -> Result<InstallMethod, String> {
+    let template = template::get_template_by_id(template_id)
+        .ok_or_else(|| format!("Unknown tool ID: {}", template_id))?;
+    let methods = template.install_methods.unwrap_or_default();
+
+    let method = match method_index {
+        Some(idx) => methods
+            .into_iter()
+            .nth(idx)
+            .ok_or_else(|| format!("'{}' has no install method #{}", template_id, idx))?,
+        None => methods
+            .into_iter()
+            .find(|m| m.method_type == "script")
+            .ok_or_else(|| format!("'{}' has no script install method", template_id))?,
+    };
+    if method.method_type != "script" {
+        return Err(format!("Install method #{} of '{}' is not a script", method_index.unwrap_or_default(), template_id));
+    }
+    Ok(method)
+}
+
+/// Get the commands of a template's script install method
+pub fn template_script(template_id: &str, method_index: Option<usize>) -> Result<Vec<String>, String> {
+    template_script_method(template_id, method_index)?
+        .script_commands
+        .ok_or_else(|| format!("Script install method for '{}' has no commands", template_id))
+}
+
+/// Get the uninstall commands of a template's script install method
+pub fn template_uninstall_script(template_id: &str, method_index: Option<usize>) -> Result<Vec<String>, String> {
+    template_script_method(template_id, method_index)?
+        .uninstall_commands
+        .ok_or_else(|| format!("Script install method for '{}' has no uninstall commands", template_id))
+}
+
+fn run_commands(commands: &[String], emit: &EventSink) -> Result<(), String> {
+    script_safety::check_script_allowed(commands)?;
+
     for (idx, command) in commands.iter().enumerate() {
-        emit_event(ScriptInstallEvent::Status {
+        emit(InstallEvent::Status {
             message: format!("Executing command {} of {}...", idx + 1, commands.len()),
         });
 
         // Execute command using shell
-        let mut cmd = if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
+        let cmd = if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
             let mut c = Command::new("sh");
             c.arg("-c").arg(command);
             c
@@ -43,48 +77,43 @@ where
             c
         };
 
-        cmd.stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        let mut child = cmd
-            .spawn()
-            .map_err(|e| format!("Failed to spawn command: {}", e))?;
+        run_streamed(cmd, &format!("Command '{}'", command), emit)?;
+    }
 
-        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-        let _stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    Ok(())
+}
 
-        let mut _emit_clone = emit_event.clone();
-        let _stdout_thread = thread::spawn(move || {
-            use std::io::{BufRead, BufReader};
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    _emit_clone(ScriptInstallEvent::Output { line });
-                }
-            }
-        });
+/// Installs a tool by running shell commands in sequence
+#[derive(Debug, Clone)]
+pub struct ScriptInstaller {
+    pub commands: Vec<String>,
+    /// Commands undoing the install, if the template provides them
+    pub uninstall_commands: Option<Vec<String>>,
+    /// Executable the script is expected to put on the PATH
+    pub executable: String,
+}
 
-        let status = child.wait()
-            .map_err(|e| format!("Failed to wait for command: {}", e))?;
+impl Installer for ScriptInstaller {
+    fn plan(&self) -> Vec<String> {
+        self.commands.clone()
+    }
 
-        _stdout_thread.join().ok();
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        execute_script_install(&self.commands, emit)
+    }
 
-        if !status.success() {
-            let error_msg = if let Some(code) = status.code() {
-                format!("Command failed with exit code {}: {}", code, command)
-            } else {
-                format!("Command terminated by signal: {}", command)
-            };
-            emit_event(ScriptInstallEvent::Error {
-                message: error_msg.clone(),
-            });
-            return Err(error_msg);
-        }
+    fn verify(&self) -> Result<bool, String> {
+        Ok(detection::which(&self.executable).is_some())
     }
 
-    emit_event(ScriptInstallEvent::Success {
-        message: "Script installation completed successfully".to_string(),
-    });
-    Ok(())
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String> {
+        let commands = self.uninstall_commands.as_ref().ok_or_else(|| {
+            format!(
+                "'{}' was installed by a script and can't be uninstalled automatically",
+                self.executable
+            )
+        })?;
+        let result = run_commands(commands, emit);
+        finish(emit, result, format!("Successfully uninstalled {}", self.executable))
+    }
 }
-
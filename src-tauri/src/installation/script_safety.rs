@@ -0,0 +1,246 @@
+use crate::types::{ScriptAnalysis, ScriptFinding, ScriptSeverity};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+/// Patterns checked by [`analyze_script`], compiled once
+struct Rules {
+    pipe_to_shell: Regex,
+    sudo: Regex,
+    rm: Regex,
+    rc_edit: Regex,
+    url: Regex,
+    pinned: Regex,
+}
+
+fn rules() -> &'static Rules {
+    static RULES: OnceLock<Rules> = OnceLock::new();
+    RULES.get_or_init(|| Rules {
+        pipe_to_shell: Regex::new(r"\b(curl|wget)\b[^|]*\|\s*(sudo\s+)?(sh|bash|zsh|dash|fish)\b").unwrap(),
+        sudo: Regex::new(r"(^|[;&|(]\s*|\s)(sudo|doas|pkexec)\s").unwrap(),
+        rm: Regex::new(r"(^|[;&|]\s*|\s)rm\s+([^;&|]*)").unwrap(),
+        rc_edit: Regex::new(
+            r"(>>?|tee\s+(-a\s+)?|sed\s+-i\S*\s+.*)\s*(~|\$HOME|\$\{HOME\})/\.(bashrc|bash_profile|zshrc|zprofile|zshenv|profile|config/fish/config\.fish)\b",
+        )
+        .unwrap(),
+        url: Regex::new(r#"https?://[^\s'"|;&)]+"#).unwrap(),
+        // A version number, a commit hash or a checksum pins the download
+        pinned: Regex::new(r"(\bv?\d+\.\d+(\.\d+)?\b|\b[0-9a-f]{40}\b|sha256)").unwrap(),
+    })
+}
+
+/// Analyze shell commands for risky patterns before running them
+///
+/// Flags piping downloads into a shell, privilege escalation, `rm -rf`
+/// outside the home directory, edits to shell startup files and downloads
+/// from unpinned or plain-HTTP URLs. Scripts with a high-severity finding
+/// get an acknowledgement token, see [`acknowledge`].
+pub fn analyze_script(commands: &[String]) -> ScriptAnalysis {
+    let rules = rules();
+    let mut findings = Vec::new();
+
+    for (index, command) in commands.iter().enumerate() {
+        let mut flag = |rule: &str, severity: ScriptSeverity, message: String| {
+            findings.push(ScriptFinding {
+                command_index: index,
+                command: command.clone(),
+                rule: rule.to_string(),
+                severity,
+                message,
+            });
+        };
+
+        if rules.pipe_to_shell.is_match(command) {
+            flag(
+                "pipe-to-shell",
+                ScriptSeverity::High,
+                "Runs a downloaded script without reviewing it".to_string(),
+            );
+        }
+        if rules.sudo.is_match(command) {
+            flag("sudo", ScriptSeverity::High, "Runs a command as root".to_string());
+        }
+        for caps in rules.rm.captures_iter(command) {
+            if let Some(target) = recursive_force_target_outside_home(&caps[2]) {
+                flag(
+                    "rm-outside-home",
+                    ScriptSeverity::High,
+                    format!("Recursively deletes {} outside the home directory", target),
+                );
+            }
+        }
+        if rules.rc_edit.is_match(command) {
+            flag(
+                "shell-rc-edit",
+                ScriptSeverity::Medium,
+                "Modifies a shell startup file".to_string(),
+            );
+        }
+        for url in rules.url.find_iter(command).map(|m| m.as_str()) {
+            if url.starts_with("http://") {
+                flag("insecure-url", ScriptSeverity::Medium, format!("Downloads over plain HTTP: {}", url));
+            } else if !rules.pinned.is_match(url) {
+                flag("unpinned-url", ScriptSeverity::Low, format!("URL isn't pinned to a version: {}", url));
+            }
+        }
+    }
+
+    let max_severity = findings.iter().map(|f| f.severity).max();
+    let acknowledgement_token = (max_severity == Some(ScriptSeverity::High)).then(|| script_token(commands));
+
+    ScriptAnalysis {
+        findings,
+        max_severity,
+        acknowledgement_token,
+    }
+}
+
+/// For the arguments of an `rm`, the first target outside `$HOME` if the
+/// removal is recursive and forced
+fn recursive_force_target_outside_home(args: &str) -> Option<String> {
+    let mut recursive = false;
+    let mut force = false;
+    let mut targets = Vec::new();
+
+    for arg in args.split_whitespace() {
+        match arg {
+            "--recursive" => recursive = true,
+            "--force" => force = true,
+            _ if arg.starts_with("--") => {}
+            _ if arg.starts_with('-') => {
+                recursive |= arg.contains('r') || arg.contains('R');
+                force |= arg.contains('f');
+            }
+            _ => targets.push(arg.trim_matches(|c| c == '"' || c == '\'')),
+        }
+    }
+    if !(recursive && force) {
+        return None;
+    }
+
+    targets
+        .into_iter()
+        .find(|target| !is_inside_home(target))
+        .map(|target| target.to_string())
+}
+
+/// Whether a path is below the home directory
+///
+/// Paths with a `..` segment could climb back out of `$HOME`, so they never
+/// count as inside it.
+fn is_inside_home(path: &str) -> bool {
+    ["~/", "$HOME/", "${HOME}/"].iter().any(|prefix| {
+        path.strip_prefix(prefix).is_some_and(|rest| {
+            let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+            !segments.is_empty() && !segments.contains(&"..")
+        })
+    })
+}
+
+/// Token identifying one exact script, the SHA-256 of its commands
+pub fn script_token(commands: &[String]) -> String {
+    hex::encode(Sha256::digest(commands.join("\n").as_bytes()))
+}
+
+fn acknowledged() -> &'static Mutex<HashSet<String>> {
+    static ACKNOWLEDGED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    ACKNOWLEDGED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Allow a high-severity script to run for the rest of the session
+///
+/// The token comes from [`analyze_script`], so the user acknowledges the
+/// exact commands they were shown.
+pub fn acknowledge(token: &str) {
+    acknowledged()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(token.to_string());
+}
+
+/// Refuse scripts with high-severity findings that weren't acknowledged
+pub fn check_script_allowed(commands: &[String]) -> Result<(), String> {
+    let analysis = analyze_script(commands);
+    let Some(token) = analysis.acknowledgement_token else {
+        return Ok(());
+    };
+
+    let is_acknowledged = acknowledged()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(&token);
+    if is_acknowledged {
+        return Ok(());
+    }
+
+    let reasons: Vec<String> = analysis
+        .findings
+        .iter()
+        .filter(|f| f.severity == ScriptSeverity::High)
+        .map(|f| f.message.clone())
+        .collect();
+    Err(format!(
+        "Script refused until it is acknowledged: {}",
+        reasons.join("; ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_of(command: &str) -> Vec<(String, ScriptSeverity)> {
+        analyze_script(&[command.to_string()])
+            .findings
+            .into_iter()
+            .map(|f| (f.rule, f.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_flags_risky_patterns() {
+        assert_eq!(
+            rules_of("curl -fsSL https://claude.ai/install.sh | bash"),
+            vec![
+                ("pipe-to-shell".to_string(), ScriptSeverity::High),
+                ("unpinned-url".to_string(), ScriptSeverity::Low),
+            ]
+        );
+        assert_eq!(rules_of("wget -qO- https://x.dev/v1.2.0/get.sh | sudo sh").len(), 2);
+        assert_eq!(rules_of("sudo apt-get install -y tmux")[0].0, "sudo");
+        assert_eq!(
+            rules_of("echo 'export A=1' >> ~/.zprofile"),
+            vec![("shell-rc-edit".to_string(), ScriptSeverity::Medium)]
+        );
+        assert_eq!(rules_of("curl -LO http://example.com/tool-1.0.tar.gz")[0].0, "insecure-url");
+    }
+
+    #[test]
+    fn test_rm_rf_only_flagged_outside_home() {
+        assert!(rules_of("rm -rf ~/.local/share/claude").is_empty());
+        assert!(rules_of("rm -f /usr/local/bin/tool").is_empty());
+        assert_eq!(rules_of("rm -rf /usr/local/lib/tool")[0].0, "rm-outside-home");
+        assert_eq!(rules_of("cd /tmp && rm -r -f build")[0].0, "rm-outside-home");
+        assert_eq!(rules_of("rm --recursive --force ~/")[0].0, "rm-outside-home");
+        assert_eq!(rules_of("rm -rf ~/../../usr")[0].0, "rm-outside-home");
+        assert_eq!(rules_of("rm -rf $HOME/../other")[0].0, "rm-outside-home");
+        assert_eq!(rules_of("rm -rf ~/./")[0].0, "rm-outside-home");
+    }
+
+    #[test]
+    fn test_high_severity_scripts_need_acknowledgement() {
+        let safe = vec!["npm install -g tree-sitter-cli".to_string()];
+        let risky = vec!["curl -fsSL https://get.example.sh | sh".to_string()];
+
+        assert!(analyze_script(&safe).acknowledgement_token.is_none());
+        assert!(check_script_allowed(&safe).is_ok());
+
+        let token = analyze_script(&risky).acknowledgement_token.unwrap();
+        assert_eq!(token, script_token(&risky));
+        assert!(check_script_allowed(&risky).unwrap_err().contains("downloaded script"));
+
+        acknowledge(&token);
+        assert!(check_script_allowed(&risky).is_ok());
+    }
+}
@@ -0,0 +1,413 @@
+use crate::cli::detection;
+use crate::installation::installer::{
+    command_from, finish, run_streamed, validate_package_name, EventSink, InstallEvent, Installer,
+};
+use crate::types::{InstallMethod, PrivilegeEscalation};
+use std::fs;
+
+/// A Linux system package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Apt,
+    Dnf,
+    Pacman,
+    Zypper,
+}
+
+impl PackageManager {
+    /// Name used as a key in `InstallMethod.system_packages`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Pacman => "pacman",
+            PackageManager::Zypper => "zypper",
+        }
+    }
+
+    /// Pick the package manager of a distribution ID, e.g. "ubuntu" or "fedora"
+    fn for_distro(id: &str) -> Option<Self> {
+        match id {
+            "debian" | "ubuntu" | "linuxmint" | "pop" | "elementary" | "raspbian" | "kali" => {
+                Some(PackageManager::Apt)
+            }
+            "fedora" | "rhel" | "centos" | "rocky" | "almalinux" | "amzn" | "ol" => {
+                Some(PackageManager::Dnf)
+            }
+            "arch" | "manjaro" | "endeavouros" | "garuda" | "artix" => Some(PackageManager::Pacman),
+            "suse" | "sles" | "opensuse" => Some(PackageManager::Zypper),
+            _ if id.starts_with("opensuse") => Some(PackageManager::Zypper),
+            _ => None,
+        }
+    }
+
+    /// Command line installing a package, without privilege escalation
+    pub fn install_args(&self, package: &str) -> Vec<String> {
+        let args: &[&str] = match self {
+            PackageManager::Apt => &["apt-get", "install", "-y"],
+            PackageManager::Dnf => &["dnf", "install", "-y"],
+            PackageManager::Pacman => &["pacman", "-S", "--noconfirm", "--needed"],
+            PackageManager::Zypper => &["zypper", "--non-interactive", "install"],
+        };
+        with_package(args, package)
+    }
+
+    /// Command line upgrading an installed package, without privilege escalation
+    pub fn upgrade_args(&self, package: &str) -> Vec<String> {
+        let args: &[&str] = match self {
+            PackageManager::Apt => &["apt-get", "install", "-y", "--only-upgrade"],
+            PackageManager::Dnf => &["dnf", "upgrade", "-y"],
+            PackageManager::Pacman => &["pacman", "-S", "--noconfirm"],
+            PackageManager::Zypper => &["zypper", "--non-interactive", "update"],
+        };
+        with_package(args, package)
+    }
+
+    /// Command line removing a package, without privilege escalation
+    pub fn uninstall_args(&self, package: &str) -> Vec<String> {
+        let args: &[&str] = match self {
+            PackageManager::Apt => &["apt-get", "remove", "-y"],
+            PackageManager::Dnf => &["dnf", "remove", "-y"],
+            PackageManager::Pacman => &["pacman", "-R", "--noconfirm"],
+            PackageManager::Zypper => &["zypper", "--non-interactive", "remove"],
+        };
+        with_package(args, package)
+    }
+
+    /// Command line checking whether a package is installed
+    ///
+    /// dnf and zypper systems are both RPM based, so they share `rpm -q`.
+    pub fn query_args(&self, package: &str) -> Vec<String> {
+        let args: &[&str] = match self {
+            PackageManager::Apt => &["dpkg-query", "-W", "-f=${Status}"],
+            PackageManager::Dnf | PackageManager::Zypper => &["rpm", "-q"],
+            PackageManager::Pacman => &["pacman", "-Q"],
+        };
+        with_package(args, package)
+    }
+
+    /// Interpret the output of a successful query command
+    ///
+    /// `dpkg-query` also knows about removed packages whose config files are
+    /// left behind, so its status has to be checked.
+    fn query_says_installed(&self, stdout: &str) -> bool {
+        match self {
+            PackageManager::Apt => stdout.contains("install ok installed"),
+            _ => true,
+        }
+    }
+}
+
+fn with_package(args: &[&str], package: &str) -> Vec<String> {
+    args.iter()
+        .map(|a| a.to_string())
+        .chain(std::iter::once(package.to_string()))
+        .collect()
+}
+
+/// The fields of `/etc/os-release` needed to pick a package manager
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsRelease {
+    pub id: String,
+    pub id_like: Vec<String>,
+}
+
+impl OsRelease {
+    /// Package manager of the distribution, falling back to the distributions it's based on
+    pub fn package_manager(&self) -> Option<PackageManager> {
+        std::iter::once(&self.id)
+            .chain(self.id_like.iter())
+            .find_map(|id| PackageManager::for_distro(id))
+    }
+}
+
+/// Parse the contents of an os-release file
+pub fn parse_os_release(content: &str) -> OsRelease {
+    let mut release = OsRelease::default();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+        match key {
+            "ID" => release.id = value.to_lowercase(),
+            "ID_LIKE" => {
+                release.id_like = value.split_whitespace().map(|v| v.to_lowercase()).collect()
+            }
+            _ => {}
+        }
+    }
+
+    release
+}
+
+/// Read the host's os-release file
+pub fn read_os_release() -> Result<OsRelease, String> {
+    let content = fs::read_to_string("/etc/os-release")
+        .or_else(|_| fs::read_to_string("/usr/lib/os-release"))
+        .map_err(|e| format!("Failed to read os-release: {}", e))?;
+    Ok(parse_os_release(&content))
+}
+
+/// Detect the host's package manager
+pub fn detect_package_manager() -> Result<PackageManager, String> {
+    let release = read_os_release()?;
+    release
+        .package_manager()
+        .ok_or_else(|| format!("Unsupported Linux distribution '{}'", release.id))
+}
+
+/// Pick the package name of a "system" install method for a distribution
+///
+/// `system_packages` entries keyed by the distribution ID win over entries
+/// keyed by the package manager, which win over `system_package`.
+pub fn package_name_for(
+    method: &InstallMethod,
+    release: &OsRelease,
+    manager: PackageManager,
+) -> Option<String> {
+    let overrides = method.system_packages.as_ref();
+    overrides
+        .and_then(|packages| packages.get(&release.id))
+        .or_else(|| overrides.and_then(|packages| packages.get(manager.as_str())))
+        .or(method.system_package.as_ref())
+        .cloned()
+}
+
+/// Prefix a command line with the configured privilege escalation wrapper
+///
+/// `sudo` runs non-interactively, since there is no terminal to ask for a
+/// password on; it needs cached credentials or a NOPASSWD rule. `pkexec`
+/// asks for the password graphically.
+pub fn escalate(args: Vec<String>, escalation: PrivilegeEscalation) -> Vec<String> {
+    let prefix: &[&str] = match escalation {
+        PrivilegeEscalation::Sudo => &["sudo", "-n"],
+        PrivilegeEscalation::Pkexec => &["pkexec"],
+        PrivilegeEscalation::None => &[],
+    };
+    prefix.iter().map(|p| p.to_string()).chain(args).collect()
+}
+
+/// Configured privilege escalation wrapper, [`default_escalation`] unless set otherwise
+pub fn configured_escalation() -> PrivilegeEscalation {
+    crate::settings::storage::load_settings()
+        .ok()
+        .and_then(|settings| settings.privilege_escalation)
+        .unwrap_or_else(default_escalation)
+}
+
+/// Escalation used when none is configured
+///
+/// Installs run from the GUI without a terminal, where `sudo -n` fails unless
+/// credentials are cached, so `pkexec` is preferred whenever it is on PATH.
+pub fn default_escalation() -> PrivilegeEscalation {
+    escalation_for(detection::which("pkexec").is_some())
+}
+
+fn escalation_for(pkexec_available: bool) -> PrivilegeEscalation {
+    if pkexec_available {
+        PrivilegeEscalation::Pkexec
+    } else {
+        PrivilegeEscalation::Sudo
+    }
+}
+
+/// Installs a tool from the distribution's package repositories
+#[derive(Debug, Clone)]
+pub struct SystemPackageInstaller {
+    pub manager: PackageManager,
+    pub package: String,
+    pub escalation: PrivilegeEscalation,
+}
+
+impl SystemPackageInstaller {
+    /// Create the installer of a "system" install method for a distribution
+    pub fn for_method(
+        method: &InstallMethod,
+        release: &OsRelease,
+        escalation: PrivilegeEscalation,
+    ) -> Result<Self, String> {
+        let manager = release
+            .package_manager()
+            .ok_or_else(|| format!("Unsupported Linux distribution '{}'", release.id))?;
+        let package = package_name_for(method, release, manager).ok_or_else(|| {
+            format!("No {} package name for distribution '{}'", manager.as_str(), release.id)
+        })?;
+        validate_package_name(&package)?;
+
+        Ok(Self {
+            manager,
+            package,
+            escalation,
+        })
+    }
+
+    /// Full install command line, including the escalation wrapper
+    pub fn install_command(&self) -> Vec<String> {
+        escalate(self.manager.install_args(&self.package), self.escalation)
+    }
+
+    /// Full upgrade command line, including the escalation wrapper
+    pub fn upgrade_command(&self) -> Vec<String> {
+        escalate(self.manager.upgrade_args(&self.package), self.escalation)
+    }
+
+    /// Full uninstall command line, including the escalation wrapper
+    pub fn uninstall_command(&self) -> Vec<String> {
+        escalate(self.manager.uninstall_args(&self.package), self.escalation)
+    }
+}
+
+impl Installer for SystemPackageInstaller {
+    fn plan(&self) -> Vec<String> {
+        vec![self.install_command().join(" ")]
+    }
+
+    fn install(&self, emit: &EventSink) -> Result<(), String> {
+        emit(InstallEvent::Status {
+            message: format!("Installing {} via {}...", self.package, self.manager.as_str()),
+        });
+        let command = self.install_command();
+        let result = command_from(&command).and_then(|cmd| run_streamed(cmd, &command.join(" "), emit));
+        finish(emit, result, format!("Successfully installed {}", self.package))
+    }
+
+    fn verify(&self) -> Result<bool, String> {
+        let args = self.manager.query_args(&self.package);
+        let output = command_from(&args)?
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", args[0], e))?;
+        Ok(output.status.success()
+            && self.manager.query_says_installed(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn upgrade(&self, emit: &EventSink) -> Result<(), String> {
+        emit(InstallEvent::Status {
+            message: format!("Upgrading {} via {}...", self.package, self.manager.as_str()),
+        });
+        let command = self.upgrade_command();
+        let result = command_from(&command).and_then(|cmd| run_streamed(cmd, &command.join(" "), emit));
+        finish(emit, result, format!("Successfully upgraded {}", self.package))
+    }
+
+    fn uninstall(&self, emit: &EventSink) -> Result<(), String> {
+        emit(InstallEvent::Status {
+            message: format!("Uninstalling {} via {}...", self.package, self.manager.as_str()),
+        });
+        let command = self.uninstall_command();
+        let result = command_from(&command).and_then(|cmd| run_streamed(cmd, &command.join(" "), emit));
+        finish(emit, result, format!("Successfully uninstalled {}", self.package))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn system_method(default: Option<&str>, overrides: &[(&str, &str)]) -> InstallMethod {
+        InstallMethod {
+            method_type: "system".to_string(),
+            brew_cask_name: None,
+            brew_formula_name: None,
+            brew_tap: None,
+            dmg_url: None,
+            dmg_install_steps: None,
+            script_commands: None,
+            github_repo: None,
+            github_asset_pattern: None,
+            system_package: default.map(|p| p.to_string()),
+            system_packages: Some(
+                overrides
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            package_name: None,
+            package_version: None,
+            runtime_name: None,
+            runtime_manager: None,
+            uninstall_commands: None,
+            sha256: None,
+            checksum_url: None,
+            checksum_pattern: None,
+            binary_path: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_os_release() {
+        let release = parse_os_release(
+            "NAME=\"Linux Mint\"\nID=linuxmint\nID_LIKE=\"ubuntu debian\"\nVERSION_ID=\"21.3\"\n",
+        );
+
+        assert_eq!(release.id, "linuxmint");
+        assert_eq!(release.id_like, vec!["ubuntu", "debian"]);
+        assert_eq!(release.package_manager(), Some(PackageManager::Apt));
+    }
+
+    #[test]
+    fn test_package_manager_falls_back_to_id_like() {
+        let release = parse_os_release("ID=nobara\nID_LIKE=\"rhel centos fedora\"\n");
+        assert_eq!(release.package_manager(), Some(PackageManager::Dnf));
+
+        let release = parse_os_release("ID=\"opensuse-tumbleweed\"\n");
+        assert_eq!(release.package_manager(), Some(PackageManager::Zypper));
+
+        let release = parse_os_release("ID=nixos\n");
+        assert_eq!(release.package_manager(), None);
+    }
+
+    #[test]
+    fn test_package_name_precedence() {
+        let method = system_method(Some("python3"), &[("pacman", "python"), ("ubuntu", "python3-full")]);
+        let ubuntu = parse_os_release("ID=ubuntu\n");
+        let arch = parse_os_release("ID=arch\n");
+        let fedora = parse_os_release("ID=fedora\n");
+
+        assert_eq!(package_name_for(&method, &ubuntu, PackageManager::Apt).as_deref(), Some("python3-full"));
+        assert_eq!(package_name_for(&method, &arch, PackageManager::Pacman).as_deref(), Some("python"));
+        assert_eq!(package_name_for(&method, &fedora, PackageManager::Dnf).as_deref(), Some("python3"));
+    }
+
+    #[test]
+    fn test_command_construction() {
+        let method = system_method(Some("tmux"), &[]);
+        let cases = [
+            ("ID=debian\n", PrivilegeEscalation::Sudo, "sudo -n apt-get install -y tmux"),
+            ("ID=fedora\n", PrivilegeEscalation::Pkexec, "pkexec dnf install -y tmux"),
+            ("ID=arch\n", PrivilegeEscalation::None, "pacman -S --noconfirm --needed tmux"),
+            ("ID=opensuse-leap\n", PrivilegeEscalation::Sudo, "sudo -n zypper --non-interactive install tmux"),
+        ];
+
+        for (os_release, escalation, expected) in cases {
+            let installer = SystemPackageInstaller::for_method(&method, &parse_os_release(os_release), escalation).unwrap();
+            assert_eq!(installer.install_command().join(" "), expected);
+        }
+
+        let debian = parse_os_release("ID=debian\n");
+        let default = SystemPackageInstaller::for_method(&method, &debian, escalation_for(true)).unwrap();
+        assert_eq!(default.install_command().join(" "), "pkexec apt-get install -y tmux");
+        let fallback = SystemPackageInstaller::for_method(&method, &debian, escalation_for(false)).unwrap();
+        assert_eq!(fallback.install_command().join(" "), "sudo -n apt-get install -y tmux");
+
+        let arch = SystemPackageInstaller::for_method(&method, &parse_os_release("ID=arch\n"), PrivilegeEscalation::Sudo).unwrap();
+        assert_eq!(arch.uninstall_command().join(" "), "sudo -n pacman -R --noconfirm tmux");
+        assert_eq!(arch.upgrade_command().join(" "), "sudo -n pacman -S --noconfirm tmux");
+        assert_eq!(PackageManager::Apt.query_args("tmux"), vec!["dpkg-query", "-W", "-f=${Status}", "tmux"]);
+        assert_eq!(PackageManager::Zypper.query_args("tmux"), vec!["rpm", "-q", "tmux"]);
+    }
+
+    #[test]
+    fn test_option_like_package_names_are_rejected() {
+        let release = parse_os_release("ID=debian\n");
+        for package in ["-oDPkg::Pre-Invoke::=touch /tmp/pwned", "--help", "tmux; rm -rf /", "tmux vim"] {
+            let method = system_method(Some(package), &[]);
+            assert!(SystemPackageInstaller::for_method(&method, &release, PrivilegeEscalation::Sudo).is_err());
+        }
+
+        let method = system_method(Some("libstdc++6"), &[]);
+        assert!(SystemPackageInstaller::for_method(&method, &release, PrivilegeEscalation::Sudo).is_ok());
+    }
+}
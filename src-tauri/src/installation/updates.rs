@@ -0,0 +1,263 @@
+use crate::cli::detection;
+use crate::dependencies::spec;
+use crate::installation::installer::{active_method_index, block_on};
+use crate::types::{CliToolDetection, CliToolTemplate, InstallMethod, ToolUpdate};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+/// Installed and latest version of an outdated package
+type Outdated = HashMap<String, (Option<String>, String)>;
+
+/// Compare installed tools against the latest version of their install source
+///
+/// Homebrew and npm are each queried once for all of their packages; GitHub
+/// releases are looked up per tool. Tools whose active install method has no
+/// version source are left out.
+///
+/// # Arguments
+/// * `templates` - Templates of the tools to check
+/// * `detections` - Detection results; only installed tools are checked
+pub fn check_updates(templates: &[CliToolTemplate], detections: &[CliToolDetection]) -> Vec<ToolUpdate> {
+    let mut brew: Option<Outdated> = None;
+    let mut npm: Option<Outdated> = None;
+    let mut updates = Vec::new();
+
+    for detection in detections.iter().filter(|d| d.installed) {
+        let Some(template) = templates.iter().find(|t| t.id == detection.template_id) else {
+            continue;
+        };
+        let Some(method) = template
+            .install_methods
+            .as_deref()
+            .and_then(|methods| methods.get(active_method_index(template)))
+        else {
+            continue;
+        };
+        let installed = detection.version.clone();
+
+        let update = match method.method_type.as_str() {
+            "brew" => {
+                let Some(package) = brew_package(method) else { continue };
+                let outdated = brew.get_or_insert_with(brew_outdated);
+                from_outdated(template, "brew", package, installed, outdated)
+            }
+            "npm" => {
+                let Some(package) = method.package_name.as_deref() else { continue };
+                let outdated = npm.get_or_insert_with(npm_outdated);
+                from_outdated(template, "npm", package, installed, outdated)
+            }
+            "github" => {
+                let Some(repo) = method.github_repo.as_deref() else { continue };
+                let latest = github_latest(repo);
+                ToolUpdate {
+                    tool_id: template.id.clone(),
+                    source: "github".to_string(),
+                    update_available: is_newer(latest.as_deref(), installed.as_deref()),
+                    installed_version: installed,
+                    latest_version: latest,
+                }
+            }
+            _ => continue,
+        };
+        updates.push(update);
+    }
+
+    updates
+}
+
+fn brew_package(method: &InstallMethod) -> Option<&str> {
+    method
+        .brew_cask_name
+        .as_deref()
+        .or(method.brew_formula_name.as_deref())
+}
+
+/// Build the update entry of a package manager that lists outdated packages
+///
+/// Packages missing from the outdated list are up to date.
+fn from_outdated(
+    template: &CliToolTemplate,
+    source: &str,
+    package: &str,
+    installed: Option<String>,
+    outdated: &Outdated,
+) -> ToolUpdate {
+    let (installed_version, latest_version, update_available) = match outdated.get(package) {
+        Some((current, latest)) => (
+            current.clone().or(installed),
+            Some(latest.clone()),
+            true,
+        ),
+        None => (installed.clone(), installed, false),
+    };
+
+    ToolUpdate {
+        tool_id: template.id.clone(),
+        source: source.to_string(),
+        installed_version,
+        latest_version,
+        update_available,
+    }
+}
+
+fn is_newer(latest: Option<&str>, installed: Option<&str>) -> bool {
+    match (latest, installed) {
+        (Some(latest), Some(installed)) => spec::compare_versions(latest, installed) == Ordering::Greater,
+        _ => false,
+    }
+}
+
+/// Run a listing command and return its stdout, even when it exits non-zero
+///
+/// `npm outdated` exits with 1 whenever something is outdated.
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    detection::which(program)?;
+    let output = Command::new(program).args(args).output().ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn brew_outdated() -> Outdated {
+    command_stdout("brew", &["outdated", "--json=v2"])
+        .map(|stdout| parse_brew_outdated(&stdout))
+        .unwrap_or_default()
+}
+
+fn npm_outdated() -> Outdated {
+    command_stdout("npm", &["outdated", "-g", "--json"])
+        .map(|stdout| parse_npm_outdated(&stdout))
+        .unwrap_or_default()
+}
+
+fn github_latest(repo: &str) -> Option<String> {
+    let (owner, name) = repo.split_once('/')?;
+    let release = block_on(crate::software::github::get_latest_release(owner, name, None))
+        .ok()?
+        .ok()?;
+    version_from_tag(&release.tag_name)
+}
+
+/// Parse `brew outdated --json=v2`
+///
+/// Formulae from taps are listed as `owner/tap/name`, so they are also keyed
+/// by their short name.
+pub fn parse_brew_outdated(output: &str) -> Outdated {
+    let json: serde_json::Value = serde_json::from_str(output).unwrap_or_default();
+    let mut outdated = HashMap::new();
+
+    for entry in ["formulae", "casks"]
+        .iter()
+        .filter_map(|key| json[key].as_array())
+        .flatten()
+    {
+        let (Some(name), Some(latest)) = (entry["name"].as_str(), entry["current_version"].as_str()) else {
+            continue;
+        };
+        // Casks used to report a single string instead of a list
+        let installed = match &entry["installed_versions"] {
+            serde_json::Value::Array(versions) => versions.last().and_then(|v| v.as_str()),
+            value => value.as_str(),
+        }
+        .map(|v| v.to_string());
+
+        let short_name = name.rsplit('/').next().unwrap_or(name);
+        outdated.insert(short_name.to_string(), (installed.clone(), latest.to_string()));
+        outdated.insert(name.to_string(), (installed, latest.to_string()));
+    }
+
+    outdated
+}
+
+/// Parse `npm outdated -g --json`
+pub fn parse_npm_outdated(output: &str) -> Outdated {
+    let json: serde_json::Value = serde_json::from_str(output).unwrap_or_default();
+    let Some(packages) = json.as_object() else {
+        return HashMap::new();
+    };
+
+    packages
+        .iter()
+        .filter_map(|(name, info)| {
+            let latest = info["latest"].as_str()?;
+            let current = info["current"].as_str().map(|v| v.to_string());
+            Some((name.clone(), (current, latest.to_string())))
+        })
+        .collect()
+}
+
+/// Extract the version from a release tag, e.g. "v1.2.3" or "jq-1.7.1"
+pub fn version_from_tag(tag: &str) -> Option<String> {
+    let start = tag.find(|c: char| c.is_ascii_digit())?;
+    Some(tag[start..].to_string())
+}
+
+/// Restrict dependency waves to the tools being upgraded
+///
+/// Dependencies that were pulled into the waves are dropped, as are waves
+/// left empty, so upgrades still run in dependency order.
+pub fn upgrade_waves(waves: Vec<Vec<String>>, tool_ids: &[String]) -> Vec<Vec<String>> {
+    let wanted: HashSet<&String> = tool_ids.iter().collect();
+    waves
+        .into_iter()
+        .map(|wave| wave.into_iter().filter(|id| wanted.contains(id)).collect::<Vec<_>>())
+        .filter(|wave| !wave.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_brew_outdated() {
+        let output = r#"{
+            "formulae": [
+                {"name": "node", "installed_versions": ["21.6.1"], "current_version": "21.7.0", "pinned": false},
+                {"name": "nikitabobko/tap/aerospace", "installed_versions": ["0.12.0"], "current_version": "0.13.0"}
+            ],
+            "casks": [
+                {"name": "wezterm", "installed_versions": "20240127", "current_version": "20240203"}
+            ]
+        }"#;
+        let outdated = parse_brew_outdated(output);
+
+        assert_eq!(outdated["node"], (Some("21.6.1".to_string()), "21.7.0".to_string()));
+        assert_eq!(outdated["aerospace"].1, "0.13.0");
+        assert_eq!(outdated["wezterm"].0.as_deref(), Some("20240127"));
+        assert!(parse_brew_outdated("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_npm_outdated() {
+        let output = r#"{
+            "@google/gemini-cli": {"current": "0.1.0", "wanted": "0.1.0", "latest": "0.2.1", "location": "/usr/lib/node_modules/@google/gemini-cli"}
+        }"#;
+        let outdated = parse_npm_outdated(output);
+
+        assert_eq!(outdated["@google/gemini-cli"], (Some("0.1.0".to_string()), "0.2.1".to_string()));
+        assert!(parse_npm_outdated("{}").is_empty());
+    }
+
+    #[test]
+    fn test_versions_from_tags() {
+        assert_eq!(version_from_tag("v14.1.0").as_deref(), Some("14.1.0"));
+        assert_eq!(version_from_tag("jq-1.7.1").as_deref(), Some("1.7.1"));
+        assert_eq!(version_from_tag("nightly"), None);
+        assert!(is_newer(Some("1.7.1"), Some("1.7")));
+        assert!(!is_newer(Some("1.7.1"), Some("1.7.1")));
+    }
+
+    #[test]
+    fn test_upgrade_waves_keep_dependency_order() {
+        let waves = vec![
+            vec!["node".to_string(), "python".to_string()],
+            vec!["codex".to_string(), "uv".to_string()],
+        ];
+        let ids = vec!["codex".to_string(), "node".to_string()];
+
+        assert_eq!(
+            upgrade_waves(waves, &ids),
+            vec![vec!["node".to_string()], vec!["codex".to_string()]]
+        );
+    }
+}
@@ -5,7 +5,6 @@ mod settings;
 mod software;
 mod types;
 
-use std::collections::HashSet;
 use std::fs;
 use tauri::Emitter;
 
@@ -58,27 +57,81 @@ fn delete_custom_template(id: String) -> Result<(), String> {
     cli::storage::delete_custom_template(&id)
 }
 
+// Template Bundle Commands
+#[tauri::command]
+fn export_templates(
+    ids: Vec<String>,
+    author: Option<String>,
+    version: Option<String>,
+) -> Result<String, String> {
+    cli::bundle::export_templates(&ids, author, version)
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn preview_template_import(path: String) -> Result<types::TemplateImportPreview, String> {
+    cli::bundle::preview_import(&path)
+}
+
+#[tauri::command]
+fn import_templates(
+    path: String,
+    resolution: types::ConflictResolution,
+) -> Result<types::TemplateImportResult, String> {
+    cli::bundle::import_templates(&path, resolution)
+}
+
 // Dependency Commands
 #[tauri::command]
-fn resolve_installation_order(tool_ids: Vec<String>) -> Result<Vec<String>, String> {
+fn resolve_installation_order(tool_ids: Vec<String>) -> Result<types::InstallationOrder, String> {
     dependencies::resolution::resolve_installation_order(&tool_ids)
 }
 
-#[tauri::command]
-fn get_dependency_tree(tool_id: String) -> Result<types::DependencyTree, String> {
-    // Get all detected tools to determine what's installed
+/// Get the versions of all templates whose tools are currently installed
+fn detect_installed_versions() -> dependencies::resolution::InstalledVersions {
     let templates = cli::template::list_all_templates();
-    let detections = cli::detection::detect_cli_tools(&templates);
-
-    let installed: HashSet<String> = detections
+    cli::detection::detect_cli_tools(&templates)
         .into_iter()
         .filter(|d| d.installed)
-        .map(|d| d.template_id)
-        .collect();
+        .map(|d| (d.template_id, d.version))
+        .collect()
+}
+
+#[tauri::command]
+fn resolve_installation_plan(tool_ids: Vec<String>) -> Result<types::InstallationPlan, String> {
+    let installed = detect_installed_versions();
+    dependencies::resolution::resolve_installation_plan(&tool_ids, &installed)
+}
+
+#[tauri::command]
+fn dry_run_installation(tool_ids: Vec<String>) -> Result<types::DryRunPlan, String> {
+    let installed = detect_installed_versions();
+    let templates = cli::template::list_all_templates();
+    installation::dry_run::dry_run(&tool_ids, &installed, &templates)
+}
 
+#[tauri::command]
+fn get_dependency_tree(tool_id: String) -> Result<types::DependencyTree, String> {
+    // Get all detected tools to determine what's installed
+    let installed = detect_installed_versions();
     dependencies::resolution::get_dependency_tree(&tool_id, &installed)
 }
 
+#[tauri::command]
+fn analyze_uninstall_impact(tool_ids: Vec<String>) -> Result<types::UninstallImpact, String> {
+    let installed = detect_installed_versions();
+    dependencies::resolution::analyze_uninstall_impact(&tool_ids, &installed)
+}
+
+#[tauri::command]
+fn export_dependency_graph(
+    format: types::GraphFormat,
+    scope: Option<Vec<String>>,
+) -> Result<String, String> {
+    let installed = detect_installed_versions();
+    dependencies::graph::export_dependency_graph(format, scope.as_deref(), &installed)
+}
+
 #[tauri::command]
 fn get_reverse_dependencies(tool_id: String) -> Result<Vec<(String, String)>, String> {
     let templates = cli::template::list_all_templates();
@@ -91,49 +144,92 @@ fn check_brew_available() -> Result<(), String> {
     installation::brew::check_brew_available()
 }
 
+/// Forward install events to the frontend as `event_name` events
+fn event_sink(app: &tauri::AppHandle, event_name: &'static str) -> installation::installer::EventSink {
+    let app = app.clone();
+    std::sync::Arc::new(move |event: installation::installer::InstallEvent| {
+        let _ = app.emit(event_name, &event);
+    })
+}
+
+/// Like [`event_sink`], but tags every payload with the tool it belongs to
+fn tool_event_sink(
+    app: &tauri::AppHandle,
+    event_name: &'static str,
+    tool_id: &str,
+) -> installation::installer::EventSink {
+    let app = app.clone();
+    let tool_id = tool_id.to_string();
+    std::sync::Arc::new(move |event: installation::installer::InstallEvent| {
+        let mut payload = event.to_payload();
+        payload["toolId"] = serde_json::json!(tool_id);
+        let _ = app.emit(event_name, &payload);
+    })
+}
+
+/// Like [`tool_event_sink`], but also writes the events to the log of `job`
+///
+/// Used by jobs that install several tools at once.
+fn job_tool_sink(
+    app: &tauri::AppHandle,
+    event_name: &'static str,
+    job: &std::sync::Arc<installation::jobs::Job>,
+    tool_id: &str,
+) -> installation::installer::EventSink {
+    let forward = tool_event_sink(app, event_name, tool_id);
+    let (job, tool_id) = (job.clone(), tool_id.to_string());
+    std::sync::Arc::new(move |event: installation::installer::InstallEvent| {
+        job.log_tool_event(&tool_id, &event);
+        forward(event);
+    })
+}
+
+#[tauri::command]
+async fn install_tool(
+    app: tauri::AppHandle,
+    template_id: String,
+    method_index: Option<usize>,
+) -> Result<(), String> {
+    let emit = tool_event_sink(&app, "install-progress", &template_id);
+    let method = installation::installer::method_type_for(&template_id, method_index);
+    installation::jobs::run_job(&emit, Some(&template_id), method.as_deref(), |_, emit| {
+        installation::installer::install_tool(&template_id, method_index, emit)
+    })
+}
+
+#[tauri::command]
+async fn uninstall_tool(
+    app: tauri::AppHandle,
+    template_id: String,
+    method_index: Option<usize>,
+    force: Option<bool>,
+    purge_config: Option<bool>,
+) -> Result<(), String> {
+    let emit = tool_event_sink(&app, "uninstall-progress", &template_id);
+    installation::jobs::run_job(&emit, Some(&template_id), Some("uninstall"), |_, emit| {
+        installation::installer::uninstall_tool(
+            &template_id,
+            method_index,
+            force.unwrap_or(false),
+            purge_config.unwrap_or(false),
+            emit,
+        )
+    })
+}
+
 #[tauri::command]
 async fn install_tool_brew(app: tauri::AppHandle, cask_name: String) -> Result<(), String> {
-    installation::brew::install_tool_brew(&cask_name, |event| {
-        // Emit event to frontend
-        let payload = match event {
-            installation::brew::BrewInstallEvent::Status { message } => {
-                serde_json::json!({ "type": "status", "message": message })
-            }
-            installation::brew::BrewInstallEvent::Output { line } => {
-                serde_json::json!({ "type": "output", "line": line })
-            }
-            installation::brew::BrewInstallEvent::Success { message } => {
-                serde_json::json!({ "type": "success", "message": message })
-            }
-            installation::brew::BrewInstallEvent::Error { message } => {
-                serde_json::json!({ "type": "error", "message": message })
-            }
-        };
-
-        let _ = app.emit("brew-install-progress", &payload);
+    let emit = event_sink(&app, "brew-install-progress");
+    installation::jobs::run_job(&emit, Some(&cask_name), Some("brew"), |_, emit| {
+        installation::brew::install_tool_brew(&cask_name, emit)
     })
 }
 
 #[tauri::command]
 async fn install_tool_brew_formula(app: tauri::AppHandle, formula_name: String) -> Result<(), String> {
-    installation::brew::install_tool_brew_formula(&formula_name, |event| {
-        // Emit event to frontend
-        let payload = match event {
-            installation::brew::BrewInstallEvent::Status { message } => {
-                serde_json::json!({ "type": "status", "message": message })
-            }
-            installation::brew::BrewInstallEvent::Output { line } => {
-                serde_json::json!({ "type": "output", "line": line })
-            }
-            installation::brew::BrewInstallEvent::Success { message } => {
-                serde_json::json!({ "type": "success", "message": message })
-            }
-            installation::brew::BrewInstallEvent::Error { message } => {
-                serde_json::json!({ "type": "error", "message": message })
-            }
-        };
-
-        let _ = app.emit("brew-install-progress", &payload);
+    let emit = event_sink(&app, "brew-install-progress");
+    installation::jobs::run_job(&emit, Some(&formula_name), Some("brew"), |_, emit| {
+        installation::brew::install_tool_brew_formula(&formula_name, emit)
     })
 }
 
@@ -144,47 +240,129 @@ async fn install_tool_brew_tap(
     package_name: String,
     is_cask: bool,
 ) -> Result<(), String> {
-    installation::brew::install_tool_brew_tap(&tap, &package_name, is_cask, |event| {
-        // Emit event to frontend
-        let payload = match event {
-            installation::brew::BrewInstallEvent::Status { message } => {
-                serde_json::json!({ "type": "status", "message": message })
-            }
-            installation::brew::BrewInstallEvent::Output { line } => {
-                serde_json::json!({ "type": "output", "line": line })
-            }
-            installation::brew::BrewInstallEvent::Success { message } => {
-                serde_json::json!({ "type": "success", "message": message })
-            }
-            installation::brew::BrewInstallEvent::Error { message } => {
-                serde_json::json!({ "type": "error", "message": message })
-            }
-        };
-
-        let _ = app.emit("brew-install-progress", &payload);
+    let emit = event_sink(&app, "brew-install-progress");
+    installation::jobs::run_job(&emit, Some(&package_name), Some("brew"), |_, emit| {
+        installation::brew::install_tool_brew_tap(&tap, &package_name, is_cask, emit)
+    })
+}
+
+#[tauri::command]
+async fn install_tool_script(
+    app: tauri::AppHandle,
+    template_id: String,
+    method_index: Option<usize>,
+) -> Result<(), String> {
+    let emit = event_sink(&app, "script-install-progress");
+    let commands = installation::script::template_script(&template_id, method_index)?;
+    installation::jobs::run_job(&emit, Some(&template_id), Some("script"), |_, emit| {
+        installation::script::execute_script_install(&commands, emit)
     })
 }
 
 #[tauri::command]
-async fn install_tool_script(app: tauri::AppHandle, commands: Vec<String>) -> Result<(), String> {
-    installation::script::execute_script_install(&commands, move |event| {
-        // Emit event to frontend
-        let payload = match event {
-            installation::script::ScriptInstallEvent::Status { message } => {
-                serde_json::json!({ "type": "status", "message": message })
-            }
-            installation::script::ScriptInstallEvent::Output { line } => {
-                serde_json::json!({ "type": "output", "line": line })
-            }
-            installation::script::ScriptInstallEvent::Success { message } => {
-                serde_json::json!({ "type": "success", "message": message })
-            }
-            installation::script::ScriptInstallEvent::Error { message } => {
-                serde_json::json!({ "type": "error", "message": message })
-            }
-        };
-
-        let _ = app.emit("script-install-progress", &payload);
+fn analyze_install_script(
+    template_id: String,
+    method_index: Option<usize>,
+    uninstall: Option<bool>,
+) -> Result<types::ScriptAnalysis, String> {
+    let commands = if uninstall.unwrap_or(false) {
+        installation::script::template_uninstall_script(&template_id, method_index)?
+    } else {
+        installation::script::template_script(&template_id, method_index)?
+    };
+    Ok(installation::script_safety::analyze_script(&commands))
+}
+
+#[tauri::command]
+fn acknowledge_install_script(token: String) {
+    installation::script_safety::acknowledge(&token)
+}
+
+#[tauri::command]
+fn cancel_install(job_id: String) -> Result<Vec<String>, String> {
+    installation::jobs::cancel_job(&job_id)
+}
+
+#[tauri::command]
+fn list_install_history() -> Result<Vec<types::InstallJobRecord>, String> {
+    installation::history::list_install_history()
+}
+
+#[tauri::command]
+fn get_install_log(job_id: String) -> Result<String, String> {
+    installation::history::get_install_log(&job_id)
+}
+
+#[tauri::command]
+async fn list_runtime_versions(runtime: String) -> Result<types::RuntimeVersions, String> {
+    installation::runtime_manager::runtime_versions(&runtime, None)
+}
+
+#[tauri::command]
+async fn get_active_runtime_version(
+    runtime: String,
+    directory: String,
+) -> Result<types::RuntimeVersions, String> {
+    installation::runtime_manager::runtime_versions(&runtime, Some(std::path::Path::new(&directory)))
+}
+
+#[tauri::command]
+async fn install_tools_parallel(
+    app: tauri::AppHandle,
+    tool_ids: Vec<String>,
+    max_parallel: Option<usize>,
+) -> Result<Vec<types::ToolInstallOutcome>, String> {
+    let templates = cli::template::list_all_templates();
+    let waves = dependencies::resolution::resolve_installation_waves_with_templates(&tool_ids, &templates)?;
+    let find = |id: &str| templates.iter().find(|t| t.id == id);
+
+    // One job covers the whole batch; every worker thread joins it
+    installation::jobs::run_job(&event_sink(&app, "install-wave-progress"), None, None, |job, _| {
+        installation::executor::execute_waves(
+            &waves,
+            max_parallel.unwrap_or(installation::executor::DEFAULT_MAX_PARALLEL),
+            |id| find(id).map(installation::executor::uses_brew).unwrap_or(false),
+            |id| find(id).map(installation::executor::dependency_ids).unwrap_or_default(),
+            |id| {
+                let template = find(id).ok_or_else(|| format!("Unknown tool ID: {}", id))?;
+                let emit = job_tool_sink(&app, "install-wave-progress", job, id);
+                installation::jobs::run_in_job(job, || {
+                    installation::executor::install_from_template(template, &emit)
+                })
+            },
+        )
+    })
+}
+
+#[tauri::command]
+async fn check_updates() -> Result<Vec<types::ToolUpdate>, String> {
+    let templates = cli::template::list_all_templates();
+    let detections = cli::detection::detect_cli_tools(&templates);
+    Ok(installation::updates::check_updates(&templates, &detections))
+}
+
+#[tauri::command]
+async fn upgrade_tools(
+    app: tauri::AppHandle,
+    tool_ids: Vec<String>,
+) -> Result<Vec<types::ToolInstallOutcome>, String> {
+    let templates = cli::template::list_all_templates();
+    let waves = dependencies::resolution::resolve_installation_waves_with_templates(&tool_ids, &templates)?;
+    let waves = installation::updates::upgrade_waves(waves, &tool_ids);
+    let find = |id: &str| templates.iter().find(|t| t.id == id);
+
+    installation::jobs::run_job(&event_sink(&app, "upgrade-progress"), None, Some("upgrade"), |job, _| {
+        installation::executor::execute_waves(
+            &waves,
+            installation::executor::DEFAULT_MAX_PARALLEL,
+            |id| find(id).map(installation::executor::uses_brew).unwrap_or(false),
+            |id| find(id).map(installation::executor::dependency_ids).unwrap_or_default(),
+            |id| {
+                let template = find(id).ok_or_else(|| format!("Unknown tool ID: {}", id))?;
+                let emit = job_tool_sink(&app, "upgrade-progress", job, id);
+                installation::jobs::run_in_job(job, || installation::installer::upgrade_tool(template, &emit))
+            },
+        )
     })
 }
 
@@ -194,17 +372,52 @@ fn get_installed_casks() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-async fn download_and_open_dmg(app: tauri::AppHandle, url: String) -> Result<String, String> {
-    installation::download::download_and_open_dmg(&url, |downloaded, total| {
-        // Emit progress event
-        let payload = serde_json::json!({
-            "type": "progress",
-            "downloaded": downloaded,
-            "total": total,
-            "percentage": if total > 0 { downloaded * 100 / total } else { 0 }
-        });
-        let _ = app.emit("dmg-download-progress", &payload);
-    }).map(|p| p.to_string_lossy().to_string())
+async fn download_and_open_dmg(
+    app: tauri::AppHandle,
+    url: String,
+    sha256: Option<String>,
+    checksum_url: Option<String>,
+) -> Result<String, String> {
+    let checksum = installation::checksum::ChecksumSource {
+        sha256,
+        checksum_url,
+        checksum_pattern: None,
+    };
+    installation::download::download_and_open_dmg(
+        &url,
+        &checksum,
+        |progress| emit_download_progress(&app, "dmg-download-progress", progress),
+        |verification| emit_download_verification(&app, "dmg-download-progress", verification),
+    )
+    .await
+    .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Emit a progress event of a download command
+fn emit_download_progress(
+    app: &tauri::AppHandle,
+    event_name: &str,
+    progress: installation::download::DownloadProgress,
+) {
+    let payload = serde_json::json!({
+        "type": "progress",
+        "downloaded": progress.downloaded,
+        "total": progress.total,
+        "resumedFrom": progress.resumed_from,
+        "percentage": if progress.total > 0 { progress.downloaded * 100 / progress.total } else { 0 }
+    });
+    let _ = app.emit(event_name, &payload);
+}
+
+/// Emit the checksum verification of a download command as a progress event
+fn emit_download_verification(
+    app: &tauri::AppHandle,
+    event_name: &str,
+    verification: &types::ChecksumVerification,
+) {
+    let mut payload = serde_json::to_value(verification).unwrap_or_default();
+    payload["type"] = serde_json::json!("verification");
+    let _ = app.emit(event_name, &payload);
 }
 
 // Environment Export/Import Commands
@@ -267,21 +480,54 @@ async fn get_github_latest_release(
     software::github::get_latest_release(&owner, &repo, asset_pattern.as_deref()).await
 }
 
+#[tauri::command]
+async fn select_github_release_asset(
+    owner: String,
+    repo: String,
+    asset_pattern: Option<String>,
+) -> Result<types::AssetSelection, String> {
+    let (_, selection) = software::github::select_latest_asset(
+        &owner,
+        &repo,
+        software::asset_selection::AssetUse::Desktop,
+        asset_pattern.as_deref(),
+    )
+    .await?;
+    Ok(selection)
+}
+
 #[tauri::command]
 async fn download_github_release_asset(
     app: tauri::AppHandle,
     url: String,
+    sha256: Option<String>,
+    checksum_url: Option<String>,
+    checksum_pattern: Option<String>,
+    app_name: Option<String>,
 ) -> Result<String, String> {
-    installation::download::download_github_asset(&url, |downloaded, total| {
-        // Emit progress event
-        let payload = serde_json::json!({
-            "type": "progress",
-            "downloaded": downloaded,
-            "total": total,
-            "percentage": if total > 0 { downloaded * 100 / total } else { 0 }
-        });
-        let _ = app.emit("github-download-progress", &payload);
-    })
+    let checksum = installation::checksum::ChecksumSource {
+        sha256,
+        checksum_url,
+        checksum_pattern,
+    };
+    let Some(app_name) = app_name else {
+        return installation::download::download_github_asset(
+            &url,
+            &checksum,
+            |progress| emit_download_progress(&app, "github-download-progress", progress),
+            |verification| emit_download_verification(&app, "github-download-progress", verification),
+        )
+        .await
+        .map(|p| p.to_string_lossy().to_string());
+    };
+    installation::download::download_github_app(
+        &url,
+        &app_name,
+        &checksum,
+        |progress| emit_download_progress(&app, "github-download-progress", progress),
+        |verification| emit_download_verification(&app, "github-download-progress", verification),
+        &event_sink(&app, "github-download-progress"),
+    )
     .await
     .map(|p| p.to_string_lossy().to_string())
 }
@@ -395,14 +641,33 @@ pub fn run() {
             list_custom_templates,
             save_custom_template,
             delete_custom_template,
+            export_templates,
+            preview_template_import,
+            import_templates,
             resolve_installation_order,
+            resolve_installation_plan,
+            dry_run_installation,
             get_dependency_tree,
             get_reverse_dependencies,
+            analyze_uninstall_impact,
+            export_dependency_graph,
             check_brew_available,
+            install_tool,
+            uninstall_tool,
             install_tool_brew,
             install_tool_brew_formula,
             install_tool_brew_tap,
             install_tool_script,
+            analyze_install_script,
+            acknowledge_install_script,
+            cancel_install,
+            list_install_history,
+            get_install_log,
+            list_runtime_versions,
+            get_active_runtime_version,
+            install_tools_parallel,
+            check_updates,
+            upgrade_tools,
             get_installed_casks,
             download_and_open_dmg,
             export_environment,
@@ -410,6 +675,7 @@ pub fn run() {
             get_software_recommendations,
             detect_installed_software,
             get_github_latest_release,
+            select_github_release_asset,
             download_github_release_asset,
             get_settings,
             save_settings,
@@ -31,6 +31,8 @@ pub fn get_default_settings() -> AppSettings {
         default_editor: None,
         proxy: None,
         github_token: None,
+        privilege_escalation: None,
+        install_history: None,
     }
 }
 
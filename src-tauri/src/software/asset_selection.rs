@@ -0,0 +1,446 @@
+use crate::types::{AssetScore, AssetSelection, GitHubReleaseAsset, GitHubReleaseInfo};
+
+/// Operating systems release assets are built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Mac,
+    Linux,
+    Windows,
+}
+
+impl Os {
+    fn label(self) -> &'static str {
+        match self {
+            Os::Mac => "macOS",
+            Os::Linux => "Linux",
+            Os::Windows => "Windows",
+        }
+    }
+}
+
+/// CPU architectures release assets are built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    X86_64,
+    Aarch64,
+    /// macOS universal binaries, running on both Intel and Apple silicon
+    Universal,
+    X86,
+    Arm,
+}
+
+impl Arch {
+    fn label(self) -> &'static str {
+        match self {
+            Arch::X86_64 => "x86_64",
+            Arch::Aarch64 => "arm64",
+            Arch::Universal => "universal",
+            Arch::X86 => "32-bit x86",
+            Arch::Arm => "32-bit ARM",
+        }
+    }
+}
+
+/// Package formats release assets come in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    Dmg,
+    Pkg,
+    AppImage,
+    Deb,
+    Rpm,
+    Msi,
+    Exe,
+    TarGz,
+    TarXz,
+    Zip,
+    /// An executable without an extension
+    Binary,
+}
+
+impl PackageKind {
+    fn label(self) -> &'static str {
+        match self {
+            PackageKind::Dmg => "dmg",
+            PackageKind::Pkg => "pkg",
+            PackageKind::AppImage => "AppImage",
+            PackageKind::Deb => "deb",
+            PackageKind::Rpm => "rpm",
+            PackageKind::Msi => "msi",
+            PackageKind::Exe => "exe",
+            PackageKind::TarGz => "tar.gz",
+            PackageKind::TarXz => "tar.xz",
+            PackageKind::Zip => "zip",
+            PackageKind::Binary => "bare executable",
+        }
+    }
+
+    /// The only OS a package format is used on, if it is specific to one
+    fn implied_os(self) -> Option<Os> {
+        match self {
+            PackageKind::Dmg | PackageKind::Pkg => Some(Os::Mac),
+            PackageKind::AppImage | PackageKind::Deb | PackageKind::Rpm => Some(Os::Linux),
+            PackageKind::Msi | PackageKind::Exe => Some(Os::Windows),
+            _ => None,
+        }
+    }
+}
+
+/// What the selected asset will be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetUse {
+    /// A command-line tool placed into `~/.local/bin`
+    CommandLine,
+    /// A GUI application installed by the user or the system package manager
+    Desktop,
+}
+
+/// The platform assets are selected for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Host {
+    pub os: Os,
+    pub arch: Arch,
+}
+
+impl Host {
+    /// The platform this app is running on
+    pub fn current() -> Self {
+        let os = match std::env::consts::OS {
+            "macos" => Os::Mac,
+            "windows" => Os::Windows,
+            _ => Os::Linux,
+        };
+        let arch = match std::env::consts::ARCH {
+            "aarch64" => Arch::Aarch64,
+            "x86" => Arch::X86,
+            "arm" => Arch::Arm,
+            _ => Arch::X86_64,
+        };
+        Host { os, arch }
+    }
+
+    /// Package formats usable on this host, most preferred first
+    fn preferred_kinds(self, asset_use: AssetUse) -> &'static [PackageKind] {
+        use PackageKind::*;
+        match (asset_use, self.os) {
+            (AssetUse::CommandLine, Os::Linux) => &[TarGz, TarXz, Binary, Zip, AppImage],
+            (AssetUse::CommandLine, _) => &[TarGz, TarXz, Zip, Binary],
+            (AssetUse::Desktop, Os::Mac) => &[Dmg, Pkg, Zip],
+            (AssetUse::Desktop, Os::Linux) => &[AppImage, Deb, Rpm, TarGz, TarXz],
+            (AssetUse::Desktop, Os::Windows) => &[Msi, Exe, Zip],
+        }
+    }
+}
+
+/// Split an asset name into lowercase tokens
+///
+/// `x86_64` and `x86-64` are kept together as `amd64`, since splitting them
+/// would leave an ambiguous `x86`.
+fn tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .replace("x86_64", "amd64")
+        .replace("x86-64", "amd64")
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_string())
+        .collect()
+}
+
+fn detect_os(tokens: &[String]) -> Option<Os> {
+    tokens.iter().find_map(|token| match token.as_str() {
+        "darwin" | "macos" | "mac" | "osx" | "apple" => Some(Os::Mac),
+        "linux" => Some(Os::Linux),
+        "windows" | "win" | "win32" | "win64" | "msvc" | "mingw" => Some(Os::Windows),
+        _ => None,
+    })
+}
+
+fn detect_arch(tokens: &[String]) -> Option<Arch> {
+    tokens.iter().find_map(|token| match token.as_str() {
+        "amd64" | "x64" | "win64" => Some(Arch::X86_64),
+        "aarch64" | "arm64" => Some(Arch::Aarch64),
+        "universal" | "universal2" => Some(Arch::Universal),
+        "i386" | "i686" | "386" | "x86" | "win32" => Some(Arch::X86),
+        "armv6" | "armv7" | "armhf" | "armv7l" | "arm" => Some(Arch::Arm),
+        _ => None,
+    })
+}
+
+fn detect_kind(name: &str) -> Option<PackageKind> {
+    let lower = name.to_lowercase();
+    let kinds = [
+        (".dmg", PackageKind::Dmg),
+        (".pkg", PackageKind::Pkg),
+        (".appimage", PackageKind::AppImage),
+        (".deb", PackageKind::Deb),
+        (".rpm", PackageKind::Rpm),
+        (".msi", PackageKind::Msi),
+        (".exe", PackageKind::Exe),
+        (".tar.gz", PackageKind::TarGz),
+        (".tgz", PackageKind::TarGz),
+        (".tar.xz", PackageKind::TarXz),
+        (".txz", PackageKind::TarXz),
+        (".zip", PackageKind::Zip),
+    ];
+    if let Some((_, kind)) = kinds.iter().find(|(ext, _)| lower.ends_with(ext)) {
+        return Some(*kind);
+    }
+
+    // Names like `jq-linux-amd64` or `yq_darwin_arm64` are bare executables;
+    // anything else with an extension (checksums, signatures) isn't a package
+    let extension = lower.rsplit_once('.').map(|(_, ext)| ext);
+    match extension {
+        None => Some(PackageKind::Binary),
+        Some(ext) if ext.chars().all(|c| c.is_ascii_digit()) => Some(PackageKind::Binary),
+        Some(_) => None,
+    }
+}
+
+/// Score one asset for the host; incompatible assets get no score
+fn score_asset(asset: &GitHubReleaseAsset, host: Host, asset_use: AssetUse) -> AssetScore {
+    let tokens = tokens(&asset.name);
+    let kind = detect_kind(&asset.name);
+    let os = detect_os(&tokens).or_else(|| kind.and_then(PackageKind::implied_os));
+    let arch = detect_arch(&tokens);
+
+    let mut score = 0;
+    let mut reasons = Vec::new();
+    let mut compatible = true;
+
+    match os {
+        Some(os) if os == host.os => {
+            score += 100;
+            reasons.push(format!("built for {}", os.label()));
+        }
+        Some(os) => {
+            compatible = false;
+            reasons.push(format!("built for {}, not {}", os.label(), host.os.label()));
+        }
+        None => {
+            score += 10;
+            reasons.push("no OS in the name".to_string());
+        }
+    }
+
+    match arch {
+        Some(arch) if arch == host.arch => {
+            score += 50;
+            reasons.push(format!("built for {}", arch.label()));
+        }
+        Some(Arch::Universal) if host.os == Os::Mac => {
+            score += 40;
+            reasons.push("universal macOS build".to_string());
+        }
+        // Apple silicon runs Intel builds through Rosetta
+        Some(Arch::X86_64) if host.os == Os::Mac && host.arch == Arch::Aarch64 => {
+            score += 20;
+            reasons.push("x86_64 build, runs through Rosetta".to_string());
+        }
+        Some(arch) => {
+            compatible = false;
+            reasons.push(format!("built for {}, not {}", arch.label(), host.arch.label()));
+        }
+        None => {
+            score += 5;
+            reasons.push("no architecture in the name".to_string());
+        }
+    }
+
+    let preferred = host.preferred_kinds(asset_use);
+    match kind.and_then(|kind| preferred.iter().position(|k| *k == kind).map(|rank| (kind, rank))) {
+        Some((kind, rank)) => {
+            score += 30 - 5 * rank as i32;
+            reasons.push(format!("{} package (preference {} of {})", kind.label(), rank + 1, preferred.len()));
+        }
+        None => {
+            compatible = false;
+            let kind = kind.map(PackageKind::label).unwrap_or("not a package");
+            reasons.push(format!("{} can't be installed here", kind));
+        }
+    }
+
+    // Statically linked Linux builds run on any distribution
+    if compatible && host.os == Os::Linux && tokens.iter().any(|t| t == "musl") {
+        score += 2;
+        reasons.push("statically linked (musl)".to_string());
+    }
+
+    AssetScore {
+        name: asset.name.clone(),
+        url: asset.browser_download_url.clone(),
+        score: compatible.then_some(score),
+        reasons,
+    }
+}
+
+/// Rank the assets of a release for a host and pick the best one
+///
+/// Assets are scored on their OS, architecture and package format, which are
+/// read from tokens in their names such as `darwin`, `arm64` or `.AppImage`.
+/// Incompatible assets are listed last without a score. With an
+/// `asset_pattern`, the first matching asset is picked instead, so templates
+/// can still override the choice.
+pub fn select_asset(
+    release: &GitHubReleaseInfo,
+    host: Host,
+    asset_use: AssetUse,
+    asset_pattern: Option<&str>,
+) -> Result<AssetSelection, String> {
+    let mut ranked: Vec<AssetScore> = release
+        .assets
+        .iter()
+        .map(|asset| score_asset(asset, host, asset_use))
+        .collect();
+    // Stable, so equally scored assets keep the release's order
+    ranked.sort_by_key(|asset| std::cmp::Reverse(asset.score));
+
+    if let Some(pattern) = asset_pattern {
+        let regex = regex::Regex::new(pattern)
+            .map_err(|e| format!("Invalid asset pattern regex: {}", e))?;
+        let selected = release.assets.iter().find(|asset| regex.is_match(&asset.name));
+        let explanation = match selected {
+            Some(asset) => format!("Picked {} because it matches the asset pattern '{}'", asset.name, pattern),
+            None => format!("No asset matches the asset pattern '{}'", pattern),
+        };
+        return Ok(AssetSelection {
+            selected: selected.map(|asset| asset.name.clone()),
+            url: selected.map(|asset| asset.browser_download_url.clone()),
+            explanation,
+            ranked,
+        });
+    }
+
+    let best = ranked.first().filter(|asset| asset.score.is_some());
+    let explanation = match best {
+        Some(asset) => format!("Picked {}: {}", asset.name, asset.reasons.join(", ")),
+        None => format!(
+            "None of the {} assets of {} can be installed on {} {}",
+            release.assets.len(),
+            release.tag_name,
+            host.os.label(),
+            host.arch.label()
+        ),
+    };
+
+    Ok(AssetSelection {
+        selected: best.map(|asset| asset.name.clone()),
+        url: best.map(|asset| asset.url.clone()),
+        explanation,
+        ranked,
+    })
+}
+
+/// Reorder assets to match a selection's ranking
+pub fn in_ranked_order(mut assets: Vec<GitHubReleaseAsset>, selection: &AssetSelection) -> Vec<GitHubReleaseAsset> {
+    assets.sort_by_key(|asset| {
+        selection
+            .ranked
+            .iter()
+            .position(|ranked| ranked.name == asset.name)
+            .unwrap_or(usize::MAX)
+    });
+    assets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(names: &[&str]) -> GitHubReleaseInfo {
+        GitHubReleaseInfo {
+            tag_name: "v1.0.0".to_string(),
+            name: None,
+            assets: names
+                .iter()
+                .map(|name| GitHubReleaseAsset {
+                    name: name.to_string(),
+                    browser_download_url: format!("https://github.com/o/r/releases/download/v1.0.0/{}", name),
+                    size: 0,
+                })
+                .collect(),
+            published_at: String::new(),
+        }
+    }
+
+    fn pick(names: &[&str], os: Os, arch: Arch, asset_use: AssetUse) -> Option<String> {
+        select_asset(&release(names), Host { os, arch }, asset_use, None).unwrap().selected
+    }
+
+    #[test]
+    fn test_picks_cli_archive_for_host() {
+        let lazygit = [
+            "checksums.txt",
+            "lazygit_0.44.1_Darwin_arm64.tar.gz",
+            "lazygit_0.44.1_Darwin_x86_64.tar.gz",
+            "lazygit_0.44.1_Linux_arm64.tar.gz",
+            "lazygit_0.44.1_Linux_x86_64.tar.gz",
+            "lazygit_0.44.1_Linux_32-bit.tar.gz",
+            "lazygit_0.44.1_Windows_x86_64.zip",
+        ];
+        assert_eq!(
+            pick(&lazygit, Os::Mac, Arch::Aarch64, AssetUse::CommandLine).as_deref(),
+            Some("lazygit_0.44.1_Darwin_arm64.tar.gz")
+        );
+        assert_eq!(
+            pick(&lazygit, Os::Linux, Arch::X86_64, AssetUse::CommandLine).as_deref(),
+            Some("lazygit_0.44.1_Linux_x86_64.tar.gz")
+        );
+        assert_eq!(
+            pick(&lazygit, Os::Windows, Arch::X86_64, AssetUse::CommandLine).as_deref(),
+            Some("lazygit_0.44.1_Windows_x86_64.zip")
+        );
+
+        let nvim = ["nvim-linux-x86_64.appimage", "nvim-linux-x86_64.tar.gz", "nvim-macos-arm64.tar.gz", "shasum.txt"];
+        assert_eq!(
+            pick(&nvim, Os::Linux, Arch::X86_64, AssetUse::CommandLine).as_deref(),
+            Some("nvim-linux-x86_64.tar.gz")
+        );
+        assert_eq!(pick(&nvim, Os::Linux, Arch::Aarch64, AssetUse::CommandLine), None);
+        assert_eq!(
+            pick(&["jq-linux-amd64", "jq-macos-arm64", "jq-1.7.1.tar.gz"], Os::Mac, Arch::Aarch64, AssetUse::CommandLine)
+                .as_deref(),
+            Some("jq-macos-arm64")
+        );
+    }
+
+    #[test]
+    fn test_picks_desktop_package_for_host() {
+        let wezterm = [
+            "WezTerm-macos-20240203.zip",
+            "WezTerm-20240203-x86_64.AppImage",
+            "wezterm-20240203.Ubuntu22.04.deb",
+            "wezterm-20240203-1.fedora39.x86_64.rpm",
+            "WezTerm-windows-20240203.zip",
+        ];
+        assert_eq!(
+            pick(&wezterm, Os::Linux, Arch::X86_64, AssetUse::Desktop).as_deref(),
+            Some("WezTerm-20240203-x86_64.AppImage")
+        );
+        assert_eq!(
+            pick(&wezterm, Os::Mac, Arch::Aarch64, AssetUse::Desktop).as_deref(),
+            Some("WezTerm-macos-20240203.zip")
+        );
+        assert_eq!(
+            pick(&["App-1.0-universal.dmg", "App-1.0-x64.dmg"], Os::Mac, Arch::Aarch64, AssetUse::Desktop).as_deref(),
+            Some("App-1.0-universal.dmg")
+        );
+    }
+
+    #[test]
+    fn test_explains_choice_and_honors_pattern() {
+        let assets = release(&["tool-linux-amd64.tar.gz", "tool-darwin-arm64.tar.gz", "tool.sha256"]);
+        let host = Host { os: Os::Mac, arch: Arch::Aarch64 };
+
+        let selection = select_asset(&assets, host, AssetUse::CommandLine, None).unwrap();
+        assert_eq!(selection.selected.as_deref(), Some("tool-darwin-arm64.tar.gz"));
+        assert!(selection.explanation.contains("built for macOS"));
+        assert!(selection.explanation.contains("built for arm64"));
+        assert_eq!(selection.ranked.len(), 3);
+        assert!(selection.ranked[1..].iter().all(|asset| asset.score.is_none()));
+
+        let selection = select_asset(&assets, host, AssetUse::CommandLine, Some("linux")).unwrap();
+        assert_eq!(selection.selected.as_deref(), Some("tool-linux-amd64.tar.gz"));
+        assert!(selection.explanation.contains("asset pattern"));
+        assert!(select_asset(&assets, host, AssetUse::CommandLine, Some("(")).is_err());
+    }
+}
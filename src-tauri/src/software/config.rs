@@ -231,6 +231,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -249,6 +252,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -267,6 +273,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                     crate::types::SoftwareInstallMethod {
                         method_type: "github".to_string(),
@@ -275,6 +284,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: Some("cli".to_string()),
                         asset_pattern: Some(r".*macOS_arm64\.zip$".to_string()),
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -293,6 +305,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -311,6 +326,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -329,6 +347,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -347,6 +368,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -365,6 +389,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -383,6 +410,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -401,6 +431,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -419,6 +452,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -437,6 +473,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -455,6 +494,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -473,6 +515,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -491,6 +536,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -509,6 +557,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -527,6 +578,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -545,6 +599,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -563,6 +620,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -581,6 +641,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -599,6 +662,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://apps.apple.com/app/cc-switch/id1495891706".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -617,6 +683,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://www.doubao.com/".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -635,6 +704,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://music.163.com/".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -653,6 +725,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://lemon.qq.com/".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -671,6 +746,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://apps.apple.com/app/ibar/id1452453066".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -689,6 +767,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://github.com/antigravity/antigravity".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -707,6 +788,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://apps.apple.com/app/cherry-studio/id1234567890".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -725,6 +809,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://github.com/flclash/flclash".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -743,6 +830,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://apps.apple.com/app/ihosts/id1102004240".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -761,6 +851,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://github.com/autoglm/autoglm".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -779,6 +872,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://www.google.com/chrome/".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -797,6 +893,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://apps.apple.com/app/rar-extractor-unarchiver/id1234567890".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -815,6 +914,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://www.wechat.com/".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -833,6 +935,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://github.com/ima-copilot/ima-copilot".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -851,6 +956,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: None,
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
@@ -869,6 +977,9 @@ fn get_default_config() -> SoftwareRecommendationsConfig {
                         repo: None,
                         asset_pattern: None,
                         url: Some("https://todo.microsoft.com/".to_string()),
+                        sha256: None,
+                        checksum_url: None,
+                        checksum_pattern: None,
                     },
                 ],
                 installed: false,
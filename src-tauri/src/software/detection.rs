@@ -1,4 +1,5 @@
 use crate::installation::brew;
+use crate::software::linux_app;
 use crate::types::SoftwareRecommendation;
 use std::fs;
 use std::path::Path;
@@ -71,7 +72,12 @@ pub fn detect_installed_software(
                     }
                 }
                 "github" => {
-                    if check_app_installed(&software.name) {
+                    let installed = if cfg!(target_os = "linux") {
+                        linux_app::is_installed(&software.name)
+                    } else {
+                        check_app_installed(&software.name)
+                    };
+                    if installed {
                         is_installed = true;
                         break;
                     }
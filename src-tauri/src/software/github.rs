@@ -1,4 +1,5 @@
-use crate::types::GitHubReleaseInfo;
+use super::asset_selection::{self, AssetUse, Host};
+use crate::types::{AssetSelection, GitHubReleaseInfo};
 use regex::Regex;
 use std::fs;
 use std::path::PathBuf;
@@ -94,11 +95,43 @@ struct CachedRelease {
 }
 
 /// Fetch the latest release from GitHub API
+///
+/// Releases are cached with all of their assets; `asset_pattern` only filters
+/// the assets of the returned release. Without a pattern, the assets are
+/// ordered by how well they fit this host as a desktop app, best first.
 pub async fn get_latest_release(
     owner: &str,
     repo: &str,
     asset_pattern: Option<&str>,
 ) -> Result<GitHubReleaseInfo, String> {
+    eprintln!("[GitHub Debug] Asset pattern: {:?}", asset_pattern);
+    let mut release = fetch_latest_release(owner, repo).await?;
+    if asset_pattern.is_none() {
+        let selection = asset_selection::select_asset(&release, Host::current(), AssetUse::Desktop, None)?;
+        eprintln!("[GitHub Debug] {}", selection.explanation);
+        release.assets = asset_selection::in_ranked_order(release.assets, &selection);
+    }
+    filter_assets(release, asset_pattern)
+}
+
+/// Pick the asset of the latest release that fits this host
+///
+/// `asset_pattern` overrides the automatic choice, see
+/// [`asset_selection::select_asset`].
+pub async fn select_latest_asset(
+    owner: &str,
+    repo: &str,
+    asset_use: AssetUse,
+    asset_pattern: Option<&str>,
+) -> Result<(GitHubReleaseInfo, AssetSelection), String> {
+    let release = fetch_latest_release(owner, repo).await?;
+    let selection = asset_selection::select_asset(&release, Host::current(), asset_use, asset_pattern)?;
+    eprintln!("[GitHub Debug] {}", selection.explanation);
+    Ok((release, selection))
+}
+
+/// Fetch the latest release with all of its assets, from the cache if fresh
+async fn fetch_latest_release(owner: &str, repo: &str) -> Result<GitHubReleaseInfo, String> {
     let cache_path = get_cache_path(owner, repo)?;
 
     // Try to load from cache first
@@ -111,7 +144,6 @@ pub async fn get_latest_release(
     // Fetch from GitHub API
     let url = format!("https://api.github.com/repos/{}/{}/releases/latest", owner, repo);
     eprintln!("[GitHub Debug] Fetching from: {}", url);
-    eprintln!("[GitHub Debug] Asset pattern: {:?}", asset_pattern);
 
     // Create HTTP client with proxy if configured
     let mut client_builder = reqwest::Client::builder()
@@ -164,7 +196,7 @@ pub async fn get_latest_release(
         if text.contains("rate limit exceeded") {
             eprintln!("[GitHub Debug] Rate limit exceeded, using HTML fallback");
             // Fall back to HTML scraping
-            let result = fetch_from_html(owner, repo, asset_pattern).await;
+            let result = fetch_from_html(owner, repo).await;
             // Cache the result from HTML scraping
             if let Ok(ref release) = result {
                 let _ = save_cached_release(&cache_path, release);
@@ -201,48 +233,48 @@ pub async fn get_latest_release(
         eprintln!("[GitHub Debug]   - {}", asset.name);
     }
 
-    // Filter assets by pattern if provided
-    let mut filtered_release = release.clone();
-    if let Some(pattern) = asset_pattern {
-        eprintln!("[GitHub Debug] Applying pattern: {}", pattern);
-        let regex = Regex::new(pattern)
-            .map_err(|e| {
-                eprintln!("[GitHub Debug] Invalid regex: {}", e);
-                format!("Invalid asset pattern regex: {}", e)
-            })?;
-
-        filtered_release.assets = release
-            .assets
-            .into_iter()
-            .filter(|asset| {
-                let matches = regex.is_match(&asset.name);
-                eprintln!("[GitHub Debug]     {} matches: {}", asset.name, matches);
-                matches
-            })
-            .collect();
-
-        eprintln!("[GitHub Debug] Filtered to {} assets", filtered_release.assets.len());
-
-        if filtered_release.assets.is_empty() {
-            return Err(format!(
-                "No assets found matching pattern: {}",
-                pattern
-            ));
-        }
-    }
-
     // Save to cache
-    let _ = save_cached_release(&cache_path, &filtered_release);
+    let _ = save_cached_release(&cache_path, &release);
 
-    Ok(filtered_release)
+    Ok(release)
 }
 
-/// Fallback: Scrape the GitHub releases HTML page when API rate limit is hit
-async fn fetch_from_html(
-    owner: &str,
-    repo: &str,
+/// Keep only the assets whose name matches `asset_pattern`, if one is given
+fn filter_assets(
+    mut release: GitHubReleaseInfo,
     asset_pattern: Option<&str>,
 ) -> Result<GitHubReleaseInfo, String> {
+    let Some(pattern) = asset_pattern else {
+        return Ok(release);
+    };
+
+    eprintln!("[GitHub Debug] Applying pattern: {}", pattern);
+    let regex = Regex::new(pattern)
+        .map_err(|e| {
+            eprintln!("[GitHub Debug] Invalid regex: {}", e);
+            format!("Invalid asset pattern regex: {}", e)
+        })?;
+
+    release.assets.retain(|asset| {
+        let matches = regex.is_match(&asset.name);
+        eprintln!("[GitHub Debug]     {} matches: {}", asset.name, matches);
+        matches
+    });
+
+    eprintln!("[GitHub Debug] Filtered to {} assets", release.assets.len());
+
+    if release.assets.is_empty() {
+        return Err(format!(
+            "No assets found matching pattern: {}",
+            pattern
+        ));
+    }
+
+    Ok(release)
+}
+
+/// Fallback: Scrape the GitHub releases HTML page when API rate limit is hit
+async fn fetch_from_html(owner: &str, repo: &str) -> Result<GitHubReleaseInfo, String> {
     eprintln!("[GitHub Debug] Using HTML scraping fallback");
 
     let url = format!("https://github.com/{}/{}/releases/latest", owner, repo);
@@ -325,39 +357,10 @@ async fn fetch_from_html(
         eprintln!("[GitHub Debug]   - {}", asset.name);
     }
 
-    // Filter by pattern if provided
-    let mut filtered_release = GitHubReleaseInfo {
+    Ok(GitHubReleaseInfo {
         tag_name: tag_name.clone(),
         name: Some(tag_name),
         assets,
         published_at: chrono::Utc::now().to_rfc3339(),
-    };
-
-    if let Some(pattern) = asset_pattern {
-        eprintln!("[GitHub Debug] Applying pattern: {}", pattern);
-        let regex = Regex::new(pattern)
-            .map_err(|e| format!("Invalid asset pattern regex: {}", e))?;
-
-        filtered_release.assets = filtered_release
-            .assets
-            .into_iter()
-            .filter(|asset| {
-                let matches = regex.is_match(&asset.name);
-                eprintln!("[GitHub Debug]     {} matches: {}", asset.name, matches);
-                matches
-            })
-            .collect();
-
-        eprintln!("[GitHub Debug] Filtered to {} assets", filtered_release.assets.len());
-
-        if filtered_release.assets.is_empty() {
-            return Err(format!(
-                "No assets found matching pattern: {}",
-                pattern
-            ));
-        }
-    }
-
-    Ok(filtered_release)
+    })
 }
-
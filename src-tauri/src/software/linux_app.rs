@@ -0,0 +1,441 @@
+use crate::installation::installer::{command_from, run_streamed, EventSink, InstallEvent};
+use crate::installation::system_package::{self, PackageManager};
+use crate::types::PrivilegeEscalation;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Desktop application formats installed on Linux
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinuxPackage {
+    AppImage,
+    Deb,
+    Rpm,
+}
+
+impl LinuxPackage {
+    /// Detect the package format from a file name
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_lowercase();
+        if lower.ends_with(".appimage") {
+            Some(LinuxPackage::AppImage)
+        } else if lower.ends_with(".deb") {
+            Some(LinuxPackage::Deb)
+        } else if lower.ends_with(".rpm") {
+            Some(LinuxPackage::Rpm)
+        } else {
+            None
+        }
+    }
+
+    /// Name of the format, used as the install method in the history
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinuxPackage::AppImage => "appimage",
+            LinuxPackage::Deb => "deb",
+            LinuxPackage::Rpm => "rpm",
+        }
+    }
+
+    /// Whether a package manager can install this format from a file
+    fn installable_with(self, manager: PackageManager) -> bool {
+        match self {
+            LinuxPackage::AppImage => false,
+            LinuxPackage::Deb => manager == PackageManager::Apt,
+            LinuxPackage::Rpm => matches!(manager, PackageManager::Dnf | PackageManager::Zypper),
+        }
+    }
+}
+
+/// Get the directory AppImages are placed in, `~/Applications`
+pub fn get_applications_dir() -> Result<PathBuf, String> {
+    let dir = dirs::home_dir()
+        .ok_or("Failed to find home directory")?
+        .join("Applications");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create Applications directory: {}", e))?;
+    }
+
+    Ok(dir)
+}
+
+/// Get the directory of the user's desktop entries, `~/.local/share/applications`
+fn get_desktop_entries_dir() -> Result<PathBuf, String> {
+    let dir = dirs::data_dir()
+        .ok_or("Failed to find data directory")?
+        .join("applications");
+
+    // Create directory if it doesn't exist
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create applications directory: {}", e))?;
+    }
+
+    Ok(dir)
+}
+
+/// File name friendly form of an app name, e.g. "Visual-Studio-Code"
+fn slug(app_name: &str) -> String {
+    app_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Escape a desktop entry value of type string
+///
+/// Backslashes and control characters would otherwise end the line or be
+/// read as escape sequences, letting a value add keys of its own.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quote a path for the `Exec` key of a desktop entry
+///
+/// The path becomes one quoted argument with the reserved characters escaped
+/// and `%` doubled, which is then escaped like any other string value.
+fn quote_exec(path: &Path) -> String {
+    let mut quoted = String::from("\"");
+    for c in path.to_string_lossy().chars() {
+        match c {
+            '"' | '`' | '$' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '%' => quoted.push_str("%%"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    escape_value(&quoted)
+}
+
+/// Contents of the desktop entry launching an AppImage
+///
+/// `TryExec` makes desktop environments hide the entry once the AppImage is
+/// deleted.
+pub fn desktop_entry(app_name: &str, app_image: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={}\n\
+         Exec={} %U\n\
+         TryExec={}\n\
+         Terminal=false\n\
+         Categories=Utility;\n\
+         X-Dev-Env-Helper=true\n",
+        escape_value(app_name),
+        quote_exec(app_image),
+        escape_value(&app_image.to_string_lossy())
+    )
+}
+
+/// Move a downloaded AppImage into `apps_dir` and add a desktop entry for it
+/// to `entries_dir`
+///
+/// The AppImage is named after the app rather than the release, so a newer
+/// download replaces the previous one.
+fn install_appimage_in(
+    downloaded: &Path,
+    app_name: &str,
+    apps_dir: &Path,
+    entries_dir: &Path,
+) -> Result<PathBuf, String> {
+    let slug = slug(app_name);
+    if slug.is_empty() || app_name.chars().any(char::is_control) {
+        return Err(format!("Can't name an AppImage after '{}'", app_name.escape_debug()));
+    }
+    let destination = apps_dir.join(format!("{}.AppImage", slug));
+
+    // The staging directory may be on another filesystem
+    if fs::rename(downloaded, &destination).is_err() {
+        fs::copy(downloaded, &destination)
+            .map_err(|e| format!("Failed to copy AppImage to {}: {}", destination.display(), e))?;
+        let _ = fs::remove_file(downloaded);
+    }
+    crate::installation::download::set_executable(&destination)?;
+
+    let entry = entries_dir.join(format!("dev-env-helper-{}.desktop", slug.to_lowercase()));
+    fs::write(&entry, desktop_entry(app_name, &destination))
+        .map_err(|e| format!("Failed to write {}: {}", entry.display(), e))?;
+
+    Ok(destination)
+}
+
+/// Place an AppImage into `~/Applications` and add it to the app menu
+pub fn install_appimage(downloaded: &Path, app_name: &str) -> Result<PathBuf, String> {
+    let entries_dir = get_desktop_entries_dir()?;
+    let placed = install_appimage_in(downloaded, app_name, &get_applications_dir()?, &entries_dir)?;
+
+    // Not every desktop needs its cache refreshed, so this may be missing
+    let _ = std::process::Command::new("update-desktop-database")
+        .arg(&entries_dir)
+        .output();
+
+    Ok(placed)
+}
+
+/// Command line installing a `.deb` or `.rpm` file with the system package manager
+///
+/// apt, dnf and zypper all install local files when given a path instead of
+/// a package name.
+pub fn package_install_command(
+    package: LinuxPackage,
+    file: &Path,
+    manager: PackageManager,
+    escalation: PrivilegeEscalation,
+) -> Result<Vec<String>, String> {
+    if !package.installable_with(manager) {
+        return Err(format!(
+            "{} can't install {}, download a package for your distribution instead",
+            manager.as_str(),
+            file.file_name().and_then(|n| n.to_str()).unwrap_or_default()
+        ));
+    }
+    let path = file
+        .to_str()
+        .ok_or_else(|| format!("Invalid package path: {}", file.display()))?;
+    Ok(system_package::escalate(manager.install_args(path), escalation))
+}
+
+/// Privilege escalation for installing `.deb` and `.rpm` files
+///
+/// Downloads are started from the GUI, where `sudo -n` fails without cached
+/// credentials, so these installs ask for the password through `pkexec`
+/// unless escalation is turned off.
+pub fn package_escalation() -> Result<PrivilegeEscalation, String> {
+    match system_package::configured_escalation() {
+        PrivilegeEscalation::None => Ok(PrivilegeEscalation::None),
+        _ if system_package::default_escalation() == PrivilegeEscalation::Pkexec => Ok(PrivilegeEscalation::Pkexec),
+        _ => Err("Installing .deb and .rpm packages needs pkexec (polkit) to ask for your password; \
+                  install polkit or download the AppImage instead"
+            .to_string()),
+    }
+}
+
+/// Install a downloaded AppImage, `.deb` or `.rpm`
+///
+/// Returns where the app ended up: the placed AppImage, or the package file,
+/// which is removed once the package manager has installed it.
+pub fn install_downloaded(downloaded: &Path, app_name: &str, emit: &EventSink) -> Result<PathBuf, String> {
+    let file_name = downloaded.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let package = LinuxPackage::from_file_name(file_name)
+        .ok_or_else(|| format!("{} isn't an AppImage, .deb or .rpm", file_name))?;
+
+    if package == LinuxPackage::AppImage {
+        let placed = install_appimage(downloaded, app_name)?;
+        emit(InstallEvent::Status {
+            message: format!("Placed {}", placed.display()),
+        });
+        return Ok(placed);
+    }
+
+    let manager = system_package::detect_package_manager()?;
+    let command = package_install_command(package, downloaded, manager, package_escalation()?)?;
+    emit(InstallEvent::Status {
+        message: format!("Installing {} via {}...", file_name, manager.as_str()),
+    });
+    run_streamed(command_from(&command)?, &command.join(" "), emit)?;
+    let _ = fs::remove_file(downloaded);
+    Ok(downloaded.to_path_buf())
+}
+
+/// Directories desktop entries are installed to, user entries first
+fn desktop_entry_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::data_dir()
+        .map(|dir| dir.join("applications"))
+        .into_iter()
+        .collect();
+    dirs.extend(
+        [
+            "/usr/share/applications",
+            "/usr/local/share/applications",
+            "/var/lib/flatpak/exports/share/applications",
+        ]
+        .iter()
+        .map(PathBuf::from),
+    );
+    dirs
+}
+
+/// The unlocalized `Name` of a desktop entry
+fn desktop_entry_name(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .skip_while(|line| line.trim() != "[Desktop Entry]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| line.strip_prefix("Name="))
+        .map(str::trim)
+}
+
+/// Whether an AppImage file name belongs to the app with the given lowercase slug
+///
+/// The name has to be the app's name, optionally followed by a version or an
+/// architecture, so "Code" matches `Code-1.95.0.AppImage` but not
+/// `Code-Insiders.AppImage`.
+fn is_app_image_of(file_name: &str, app_slug: &str) -> bool {
+    let lower = file_name.to_lowercase();
+    let Some(stem) = lower.strip_suffix(".appimage") else {
+        return false;
+    };
+    let Some(rest) = slug(stem).strip_prefix(app_slug).map(str::to_string) else {
+        return false;
+    };
+    let Some(suffix) = rest.strip_prefix('-') else {
+        return rest.is_empty();
+    };
+    suffix.starts_with(|c: char| c.is_ascii_digit())
+        || ["x86", "amd64", "x64", "aarch64", "arm64", "armhf", "i386", "i686", "linux"]
+            .iter()
+            .any(|token| suffix == *token || suffix.starts_with(&format!("{}-", token)))
+}
+
+/// Check for an app in the given AppImage and desktop entry directories
+///
+/// Matching is case-insensitive, on the AppImage's file name or on the
+/// `Name` of a desktop entry, which covers apps installed from `.deb` and
+/// `.rpm` packages.
+fn is_installed_in(app_name: &str, app_image_dirs: &[PathBuf], entry_dirs: &[PathBuf]) -> bool {
+    let app_slug = slug(app_name).to_lowercase();
+    if app_slug.is_empty() {
+        return false;
+    }
+    let has_app_image = app_image_dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .any(|file_name| is_app_image_of(&file_name, &app_slug));
+    if has_app_image {
+        return true;
+    }
+
+    entry_dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "desktop"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .any(|content| desktop_entry_name(&content).is_some_and(|name| name.eq_ignore_ascii_case(app_name)))
+}
+
+/// Check if a desktop app is installed on Linux
+pub fn is_installed(app_name: &str) -> bool {
+    let app_image_dirs: Vec<PathBuf> = dirs::home_dir()
+        .map(|home| vec![home.join("Applications"), home.join(".local").join("bin")])
+        .unwrap_or_default();
+    is_installed_in(app_name, &app_image_dirs, &desktop_entry_dirs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("linux-app-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_package_install_command() {
+        let deb = Path::new("/tmp/wezterm-20240203.Ubuntu22.04.deb");
+        assert_eq!(LinuxPackage::from_file_name("WezTerm-x86_64.AppImage"), Some(LinuxPackage::AppImage));
+        assert_eq!(LinuxPackage::from_file_name("wezterm.fedora39.x86_64.rpm"), Some(LinuxPackage::Rpm));
+        assert_eq!(LinuxPackage::from_file_name("wezterm.tar.gz"), None);
+
+        assert_eq!(
+            package_install_command(LinuxPackage::Deb, deb, PackageManager::Apt, PrivilegeEscalation::Sudo).unwrap(),
+            vec!["sudo", "-n", "apt-get", "install", "-y", "/tmp/wezterm-20240203.Ubuntu22.04.deb"]
+        );
+        assert_eq!(
+            package_install_command(LinuxPackage::Rpm, Path::new("/tmp/a.rpm"), PackageManager::Zypper, PrivilegeEscalation::None)
+                .unwrap(),
+            vec!["zypper", "--non-interactive", "install", "/tmp/a.rpm"]
+        );
+        assert!(package_install_command(LinuxPackage::Deb, deb, PackageManager::Dnf, PrivilegeEscalation::Sudo).is_err());
+        assert!(package_install_command(LinuxPackage::Rpm, deb, PackageManager::Pacman, PrivilegeEscalation::Sudo).is_err());
+    }
+
+    #[test]
+    fn test_install_appimage_is_detected() {
+        let dir = scratch_dir("appimage");
+        let (apps_dir, entries_dir) = (dir.join("Applications"), dir.join("applications"));
+        fs::create_dir_all(&apps_dir).unwrap();
+        fs::create_dir_all(&entries_dir).unwrap();
+        let downloaded = dir.join("Visual-Studio-Code-1.95.0-x86_64.AppImage");
+        fs::write(&downloaded, b"appimage").unwrap();
+
+        assert!(!is_installed_in("Visual Studio Code", std::slice::from_ref(&apps_dir), std::slice::from_ref(&entries_dir)));
+        let placed = install_appimage_in(&downloaded, "Visual Studio Code", &apps_dir, &entries_dir).unwrap();
+        assert_eq!(placed, apps_dir.join("Visual-Studio-Code.AppImage"));
+        assert!(!downloaded.exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&placed).unwrap().permissions().mode() & 0o111, 0o111);
+        }
+
+        let entry = fs::read_to_string(entries_dir.join("dev-env-helper-visual-studio-code.desktop")).unwrap();
+        assert!(entry.contains("Name=Visual Studio Code\n"));
+        assert!(entry.contains(&format!("Exec=\"{}\" %U\n", placed.display())));
+
+        assert!(is_installed_in("visual studio code", std::slice::from_ref(&apps_dir), &[]));
+        assert!(is_installed_in("Visual Studio Code", &[], std::slice::from_ref(&entries_dir)));
+        assert!(!is_installed_in("Visual Studio", &[], &[entries_dir]));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_desktop_entry_values_are_escaped() {
+        let entry = desktop_entry("Evil\nExec=rm -rf ~", Path::new("/home/me/My Apps/50% \"off\".AppImage"));
+        let lines: Vec<&str> = entry.lines().collect();
+
+        assert!(lines.contains(&"Name=Evil\\nExec=rm -rf ~"));
+        assert_eq!(lines.iter().filter(|line| line.starts_with("Exec=")).count(), 1);
+        assert!(lines.contains(&r#"Exec="/home/me/My Apps/50%% \\"off\\".AppImage" %U"#));
+
+        let dir = scratch_dir("control-name");
+        let downloaded = dir.join("tool.AppImage");
+        fs::write(&downloaded, b"appimage").unwrap();
+        assert!(install_appimage_in(&downloaded, "Tool\nExec=x", &dir, &dir).is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_app_image_names_match_exactly() {
+        assert!(is_app_image_of("Code.AppImage", "code"));
+        assert!(is_app_image_of("code-1.95.0.AppImage", "code"));
+        assert!(is_app_image_of("Code-x86_64.AppImage", "code"));
+        assert!(!is_app_image_of("Code-Insiders.AppImage", "code"));
+        assert!(!is_app_image_of("Codex.AppImage", "code"));
+        assert!(!is_app_image_of("code.tar.gz", "code"));
+        assert!(!is_installed_in("", &[std::env::temp_dir()], &[]));
+        assert!(install_appimage_in(Path::new("/nonexistent"), " ", Path::new("/tmp"), Path::new("/tmp")).is_err());
+    }
+
+    #[test]
+    fn test_desktop_entry_name_ignores_translations_and_actions() {
+        let content = "[Desktop Entry]\nName[de]=Rechner\nName=Calculator\n[Desktop Action New]\nName=New Window\n";
+        assert_eq!(desktop_entry_name(content), Some("Calculator"));
+        assert_eq!(desktop_entry_name("[Desktop Action New]\nName=New Window\n"), None);
+        // `\$` quotes the `$`, and the backslash is escaped again as a string value
+        assert_eq!(quote_exec(Path::new("/home/a b/$x.AppImage")), "\"/home/a b/\\\\$x.AppImage\"");
+    }
+}
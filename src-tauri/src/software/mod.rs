@@ -1,5 +1,7 @@
+pub mod asset_selection;
 pub mod config;
 pub mod detection;
 pub mod github;
+pub mod linux_app;
 
 
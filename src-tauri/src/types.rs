@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CliToolTemplate {
@@ -36,6 +37,46 @@ pub struct InstallMethod {
     pub dmg_install_steps: Option<Vec<String>>,
     #[serde(rename = "scriptCommands")]
     pub script_commands: Option<Vec<String>>,
+    /// Repository of a "github" install method, in `owner/repo` form
+    #[serde(rename = "githubRepo", default, skip_serializing_if = "Option::is_none")]
+    pub github_repo: Option<String>,
+    #[serde(rename = "githubAssetPattern", default, skip_serializing_if = "Option::is_none")]
+    pub github_asset_pattern: Option<String>,
+    /// Package name of a "system" install method
+    #[serde(rename = "systemPackage", default, skip_serializing_if = "Option::is_none")]
+    pub system_package: Option<String>,
+    /// Package names by distribution ID ("ubuntu") or package manager ("pacman")
+    #[serde(rename = "systemPackages", default, skip_serializing_if = "Option::is_none")]
+    pub system_packages: Option<HashMap<String, String>>,
+    /// Package of an npm, pipx, uv-tool or cargo method, module path for go
+    #[serde(rename = "packageName", default, skip_serializing_if = "Option::is_none")]
+    pub package_name: Option<String>,
+    /// Version to install, latest when unset
+    #[serde(rename = "packageVersion", default, skip_serializing_if = "Option::is_none")]
+    pub package_version: Option<String>,
+    /// Runtime of a "runtime-manager" install method, e.g. "node"
+    #[serde(rename = "runtimeName", default, skip_serializing_if = "Option::is_none")]
+    pub runtime_name: Option<String>,
+    /// "mise" or "asdf", whichever is installed when unset
+    #[serde(rename = "runtimeManager", default, skip_serializing_if = "Option::is_none")]
+    pub runtime_manager: Option<String>,
+    /// Commands undoing a "script" install method
+    #[serde(rename = "uninstallCommands", default, skip_serializing_if = "Option::is_none")]
+    pub uninstall_commands: Option<Vec<String>>,
+    /// Expected SHA-256 of the file a "dmg" or "github" method downloads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// URL of a checksum file listing the downloaded file
+    #[serde(rename = "checksumUrl", default, skip_serializing_if = "Option::is_none")]
+    pub checksum_url: Option<String>,
+    /// Regex for the checksum asset of a GitHub release, when the usual
+    /// names like `checksums.txt` or `SHA256SUMS` don't match
+    #[serde(rename = "checksumPattern", default, skip_serializing_if = "Option::is_none")]
+    pub checksum_pattern: Option<String>,
+    /// Path of the executable inside the archive of a "github" install method,
+    /// e.g. "bin/nvim"; found by the template's executable name when unset
+    #[serde(rename = "binaryPath", default, skip_serializing_if = "Option::is_none")]
+    pub binary_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +113,22 @@ pub struct DependencyNode {
     pub name: String,
     pub installed: bool,
     pub dependencies: Vec<DependencyNode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The dependency entry this node was chosen for, when it isn't a plain tool ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(rename = "constraintSatisfied", skip_serializing_if = "Option::is_none")]
+    pub constraint_satisfied: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyCycleMember {
+    #[serde(rename = "toolId")]
+    pub tool_id: String,
+    pub name: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +140,284 @@ pub struct DependencyTree {
     pub installed_count: usize,
     #[serde(rename = "missingCount")]
     pub missing_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle: Option<Vec<DependencyCycleMember>>,
+    #[serde(rename = "unsatisfiedConstraints", default, skip_serializing_if = "Vec::is_empty")]
+    pub unsatisfied_constraints: Vec<String>,
+}
+
+/// Tool IDs in the order they should be installed
+///
+/// Ordering guarantees:
+/// * Every tool comes after all of its dependencies in the set.
+/// * The order is deterministic: the same input always yields the same order.
+/// * Among tools whose dependencies are all satisfied, the one requested
+///   earliest by the caller comes first. Tools that were not requested
+///   (pulled-in dependencies) follow, ordered by category (uncategorized
+///   last) and then by ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InstallationOrder(pub Vec<String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanItemReason {
+    Requested,
+    Dependency,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallationPlanItem {
+    #[serde(rename = "toolId")]
+    pub tool_id: String,
+    pub name: String,
+    pub reason: PlanItemReason,
+    #[serde(rename = "requiredBy")]
+    pub required_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallationPlan {
+    pub items: Vec<InstallationPlanItem>,
+    #[serde(rename = "alreadyInstalled")]
+    pub already_installed: Vec<String>,
+    /// How dependencies with alternatives, constraints or `?` were resolved
+    pub choices: Vec<DependencyChoice>,
+    /// Dependency cycle preventing the install; `items` is empty when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle: Option<Vec<DependencyCycleMember>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyChoice {
+    #[serde(rename = "toolId")]
+    pub tool_id: String,
+    pub dependency: String,
+    pub chosen: Option<String>,
+    pub reason: String,
+    pub satisfied: bool,
+}
+
+/// What installing a set of tools would run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunPlan {
+    /// Tool IDs in the order their steps would run
+    pub order: Vec<String>,
+    pub steps: Vec<DryRunStep>,
+    /// How dependencies with alternatives, constraints or `?` were resolved
+    pub choices: Vec<DependencyChoice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunStep {
+    pub tool_id: String,
+    pub name: String,
+    /// Install method that would be used, e.g. "brew"
+    pub method: Option<String>,
+    /// Commands, downloads and destinations, in execution order
+    pub commands: Vec<String>,
+    /// Environment variables set for the commands, with credentials redacted
+    pub environment: HashMap<String, String>,
+    pub skipped: bool,
+    /// Why the step is skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An installed tool that stops working when the removal set is uninstalled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedTool {
+    #[serde(rename = "toolId")]
+    pub tool_id: String,
+    pub name: String,
+    /// Removed or broken tools it depends on
+    #[serde(rename = "dependsOn")]
+    pub depends_on: Vec<String>,
+    /// Whether it depends on the removal set itself rather than on another broken tool
+    pub direct: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UninstallImpact {
+    /// The tools to remove, dependents before their dependencies
+    #[serde(rename = "removalOrder")]
+    pub removal_order: Vec<String>,
+    pub affected: Vec<AffectedTool>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    pub installed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// A "depends on" edge, from the dependent tool to its dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// Version constraints on the dependency, e.g. ">= 18"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    pub optional: bool,
+    /// Whether the dependency can also be satisfied by another tool
+    pub alternative: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeVersions {
+    /// Version manager that was queried, "mise" or "asdf"
+    pub manager: String,
+    pub runtime: String,
+    pub installed: Vec<String>,
+    /// Version active in the requested directory
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolInstallStatus {
+    Installed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInstallOutcome {
+    #[serde(rename = "toolId")]
+    pub tool_id: String,
+    pub wave: usize,
+    pub status: ToolInstallStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallJobStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// A persisted install job, without its output log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallJobRecord {
+    pub job_id: String,
+    /// Tool or package being installed, unset for batch installs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Install method, e.g. "brew" or "script"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Command lines in the order they were started
+    pub commands: Vec<String>,
+    pub started_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    pub status: InstallJobStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Files the app placed on disk for one tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Install method that placed the files, e.g. "github"
+    pub method: String,
+    pub files: Vec<String>,
+    pub installed_at: String,
+}
+
+/// Installed and latest version of a tool from its install source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolUpdate {
+    pub tool_id: String,
+    /// Where the latest version came from: "brew", "npm" or "github"
+    pub source: String,
+    pub installed_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumStatus {
+    Verified,
+    /// The file didn't match and was quarantined
+    Mismatch,
+    /// No checksum was published for the file
+    Unverified,
+}
+
+/// Outcome of checking a download against its expected SHA-256
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumVerification {
+    pub status: ChecksumStatus,
+    /// Where the expected checksum came from: "configured" or a checksum file URL
+    pub source: Option<String>,
+    pub expected: Option<String>,
+    pub actual: String,
+    /// Where a file that failed verification was moved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quarantined_to: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+/// A risky pattern found in one command of an install script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptFinding {
+    pub command_index: usize,
+    pub command: String,
+    /// Rule that matched, e.g. "pipe-to-shell"
+    pub rule: String,
+    pub severity: ScriptSeverity,
+    pub message: String,
+}
+
+/// Result of analyzing an install script before running it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptAnalysis {
+    pub findings: Vec<ScriptFinding>,
+    pub max_severity: Option<ScriptSeverity>,
+    /// Token to acknowledge before a high-severity script is allowed to run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledgement_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +434,60 @@ pub struct EnvironmentExport {
     pub software: Option<Vec<SoftwareRecommendation>>,
 }
 
+// Template Bundle Types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateBundleMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub created: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateBundle {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub metadata: TemplateBundleMetadata,
+    pub templates: Vec<CliToolTemplate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictResolution {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateImportConflict {
+    #[serde(rename = "templateId")]
+    pub template_id: String,
+    #[serde(rename = "existingName")]
+    pub existing_name: String,
+    #[serde(rename = "incomingName")]
+    pub incoming_name: String,
+    #[serde(rename = "isBuiltin")]
+    pub is_builtin: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateImportPreview {
+    pub metadata: TemplateBundleMetadata,
+    pub templates: Vec<CliToolTemplate>,
+    pub conflicts: Vec<TemplateImportConflict>,
+    #[serde(rename = "missingDependencies")]
+    pub missing_dependencies: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateImportResult {
+    pub imported: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+    pub skipped: Vec<String>,
+}
+
 // Software Recommendations Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SoftwareCategory {
@@ -117,6 +506,15 @@ pub struct SoftwareInstallMethod {
     #[serde(rename = "assetPattern")]
     pub asset_pattern: Option<String>,
     pub url: Option<String>,
+    /// Expected SHA-256 of the downloaded file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// URL of a checksum file listing the downloaded file
+    #[serde(rename = "checksumUrl", default, skip_serializing_if = "Option::is_none")]
+    pub checksum_url: Option<String>,
+    /// Regex for the checksum asset of a GitHub release
+    #[serde(rename = "checksumPattern", default, skip_serializing_if = "Option::is_none")]
+    pub checksum_pattern: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,6 +554,28 @@ pub struct GitHubReleaseInfo {
     pub published_at: String,
 }
 
+/// How well one release asset fits the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetScore {
+    pub name: String,
+    pub url: String,
+    /// None when the asset can't be installed on the host
+    pub score: Option<i32>,
+    /// What the score is based on, e.g. "built for Linux"
+    pub reasons: Vec<String>,
+}
+
+/// The asset picked from a release, with all assets ranked best first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetSelection {
+    pub selected: Option<String>,
+    pub url: Option<String>,
+    pub explanation: String,
+    pub ranked: Vec<AssetScore>,
+}
+
 // Settings Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -168,6 +588,31 @@ pub struct AppSettings {
     pub proxy: Option<ProxySettings>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privilege_escalation: Option<PrivilegeEscalation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_history: Option<InstallHistorySettings>,
+}
+
+/// How long finished install jobs are kept in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallHistorySettings {
+    /// Maximum number of jobs to keep, newest first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_jobs: Option<usize>,
+    /// Jobs older than this many days are removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+}
+
+/// How system package managers get root privileges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivilegeEscalation {
+    Sudo,
+    Pkexec,
+    None,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
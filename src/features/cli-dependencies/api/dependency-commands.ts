@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { DependencyTree } from "../types/dependency";
+import type { DependencyTree, InstallationPlan } from "../types/dependency";
 
 export async function getDependencyTree(toolId: string): Promise<DependencyTree> {
   return await invoke("get_dependency_tree", { toolId });
@@ -9,6 +9,10 @@ export async function resolveInstallationOrder(toolIds: string[]): Promise<strin
   return await invoke("resolve_installation_order", { toolIds });
 }
 
+export async function resolveInstallationPlan(toolIds: string[]): Promise<InstallationPlan> {
+  return await invoke("resolve_installation_plan", { toolIds });
+}
+
 export async function getReverseDependencies(toolId: string): Promise<[string, string][]> {
   return await invoke("get_reverse_dependencies", { toolId });
 }
@@ -4,6 +4,15 @@ export interface DependencyNode {
   installed: boolean;
   dependencies: DependencyNode[];
   requiredBy: string[];
+  version?: string;
+  requirement?: string;
+  optional?: boolean;
+  constraintSatisfied?: boolean;
+}
+
+export interface DependencyCycleMember {
+  toolId: string;
+  name: string;
 }
 
 export interface DependencyTree {
@@ -11,6 +20,31 @@ export interface DependencyTree {
   totalTools: number;
   installedCount: number;
   missingCount: number;
+  cycle?: DependencyCycleMember[];
+  unsatisfiedConstraints?: string[];
+}
+
+export interface InstallationPlanItem {
+  toolId: string;
+  name: string;
+  reason: "requested" | "dependency";
+  requiredBy: string[];
+}
+
+export interface DependencyChoice {
+  toolId: string;
+  dependency: string;
+  chosen: string | null;
+  reason: string;
+  satisfied: boolean;
+}
+
+export interface InstallationPlan {
+  items: InstallationPlanItem[];
+  alreadyInstalled: string[];
+  choices: DependencyChoice[];
+  /** Dependency cycle preventing the install; items is empty when set */
+  cycle?: DependencyCycleMember[];
 }
 
 export interface InstallQueueItem {
@@ -2,22 +2,123 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 
 export interface BrewInstallEvent {
-  type: "status" | "output" | "success" | "error";
+  type: "started" | "status" | "output" | "success" | "error" | "cancelled";
   message?: string;
   line?: string;
+  stream?: "stdout" | "stderr";
+  timestamp?: string;
+  jobId?: string;
+  completedCommands?: string[];
 }
 
 export interface DmgDownloadEvent {
   type: "progress";
   downloaded: number;
   total: number;
+  resumedFrom: number;
   percentage: number;
 }
 
+export interface ChecksumVerification {
+  status: "verified" | "mismatch" | "unverified";
+  source: string | null;
+  expected: string | null;
+  actual: string;
+  quarantinedTo?: string;
+}
+
+export interface DownloadVerificationEvent extends ChecksumVerification {
+  type: "verification";
+}
+
 export interface ScriptInstallEvent {
-  type: "status" | "output" | "success" | "error";
+  type: "started" | "status" | "output" | "success" | "error" | "cancelled";
   message?: string;
   line?: string;
+  stream?: "stdout" | "stderr";
+  timestamp?: string;
+  jobId?: string;
+  completedCommands?: string[];
+}
+
+export interface InstallJobRecord {
+  jobId: string;
+  tool?: string;
+  method?: string;
+  commands: string[];
+  startedAt: string;
+  finishedAt?: string;
+  status: "running" | "succeeded" | "failed" | "cancelled";
+  error?: string;
+}
+
+export interface ToolUpdate {
+  toolId: string;
+  source: "brew" | "npm" | "github";
+  installedVersion: string | null;
+  latestVersion: string | null;
+  updateAvailable: boolean;
+}
+
+export interface ToolInstallOutcome {
+  toolId: string;
+  wave: number;
+  status: "installed" | "failed" | "skipped";
+  error?: string;
+}
+
+export interface DryRunStep {
+  toolId: string;
+  name: string;
+  method: string | null;
+  commands: string[];
+  environment: Record<string, string>;
+  skipped: boolean;
+  reason?: string;
+}
+
+export interface DryRunPlan {
+  order: string[];
+  steps: DryRunStep[];
+  choices: {
+    toolId: string;
+    dependency: string;
+    chosen: string | null;
+    reason: string;
+    satisfied: boolean;
+  }[];
+}
+
+export async function dryRunInstallation(toolIds: string[]): Promise<DryRunPlan> {
+  return await invoke("dry_run_installation", { toolIds });
+}
+
+export type ScriptSeverity = "low" | "medium" | "high";
+
+export interface ScriptFinding {
+  commandIndex: number;
+  command: string;
+  rule: string;
+  severity: ScriptSeverity;
+  message: string;
+}
+
+export interface ScriptAnalysis {
+  findings: ScriptFinding[];
+  maxSeverity: ScriptSeverity | null;
+  acknowledgementToken?: string;
+}
+
+export async function analyzeInstallScript(
+  templateId: string,
+  methodIndex?: number,
+  uninstall?: boolean
+): Promise<ScriptAnalysis> {
+  return await invoke("analyze_install_script", { templateId, methodIndex, uninstall });
+}
+
+export async function acknowledgeInstallScript(token: string): Promise<void> {
+  return await invoke("acknowledge_install_script", { token });
 }
 
 export async function checkBrewAvailable(): Promise<void> {
@@ -72,7 +173,8 @@ export async function installToolBrewTap(
 }
 
 export async function installToolScript(
-  commands: string[],
+  templateId: string,
+  methodIndex: number | undefined,
   onProgress: (event: ScriptInstallEvent) => void
 ): Promise<void> {
   const unlisten = await listen<ScriptInstallEvent>("script-install-progress", (event) => {
@@ -80,26 +182,78 @@ export async function installToolScript(
   });
 
   try {
-    await invoke("install_tool_script", { commands });
+    await invoke("install_tool_script", { templateId, methodIndex });
   } finally {
     unlisten();
   }
 }
 
+export async function uninstallTool(
+  templateId: string,
+  options: { methodIndex?: number; force?: boolean; purgeConfig?: boolean },
+  onProgress: (event: ScriptInstallEvent) => void
+): Promise<void> {
+  const unlisten = await listen<ScriptInstallEvent>("uninstall-progress", (event) => {
+    onProgress(event.payload);
+  });
+
+  try {
+    await invoke("uninstall_tool", { templateId, ...options });
+  } finally {
+    unlisten();
+  }
+}
+
+export async function checkUpdates(): Promise<ToolUpdate[]> {
+  return await invoke("check_updates");
+}
+
+export async function upgradeTools(
+  toolIds: string[],
+  onProgress: (event: ScriptInstallEvent & { toolId?: string }) => void
+): Promise<ToolInstallOutcome[]> {
+  const unlisten = await listen<ScriptInstallEvent & { toolId?: string }>("upgrade-progress", (event) => {
+    onProgress(event.payload);
+  });
+
+  try {
+    return await invoke("upgrade_tools", { toolIds });
+  } finally {
+    unlisten();
+  }
+}
+
+/**
+ * Cancel a running install by the job ID from its "started" event.
+ * Resolves to the commands that had completed.
+ */
+export async function cancelInstall(jobId: string): Promise<string[]> {
+  return await invoke("cancel_install", { jobId });
+}
+
+export async function listInstallHistory(): Promise<InstallJobRecord[]> {
+  return await invoke("list_install_history");
+}
+
+export async function getInstallLog(jobId: string): Promise<string> {
+  return await invoke("get_install_log", { jobId });
+}
+
 export async function getInstalledCasks(): Promise<string[]> {
   return await invoke("get_installed_casks");
 }
 
 export async function downloadAndOpenDmg(
   url: string,
-  onProgress: (event: DmgDownloadEvent) => void
+  onProgress: (event: DmgDownloadEvent | DownloadVerificationEvent) => void,
+  checksum?: { sha256?: string; checksumUrl?: string }
 ): Promise<string> {
-  const unlisten = await listen<DmgDownloadEvent>("dmg-download-progress", (event) => {
+  const unlisten = await listen<DmgDownloadEvent | DownloadVerificationEvent>("dmg-download-progress", (event) => {
     onProgress(event.payload);
   });
 
   try {
-    return await invoke("download_and_open_dmg", { url });
+    return await invoke("download_and_open_dmg", { url, ...checksum });
   } finally {
     unlisten();
   }
@@ -0,0 +1,55 @@
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { Badge } from "@/components/ui/badge";
+import type { ScriptAnalysis } from "../api/installation-commands";
+
+export interface ScriptReview {
+  toolId: string;
+  analysis: ScriptAnalysis;
+}
+
+interface ScriptReviewDialogProps {
+  review: ScriptReview | null;
+  onApprove: () => void;
+  onReject: () => void;
+}
+
+export function ScriptReviewDialog({ review, onApprove, onReject }: ScriptReviewDialogProps) {
+  return (
+    <AlertDialog open={review !== null} onOpenChange={(open) => !open && onReject()}>
+      <AlertDialogContent>
+        <AlertDialogHeader>
+          <AlertDialogTitle>Review install script</AlertDialogTitle>
+          <AlertDialogDescription>
+            The script for "{review?.toolId}" does things that can't be undone. Only run it if you trust its source.
+          </AlertDialogDescription>
+        </AlertDialogHeader>
+        <ul className="space-y-2 text-sm max-h-64 overflow-y-auto">
+          {review?.analysis.findings.map((finding, index) => (
+            <li key={index} className="flex flex-col gap-1">
+              <div className="flex items-center gap-2">
+                <Badge variant={finding.severity === "high" ? "destructive" : "outline"}>
+                  {finding.severity}
+                </Badge>
+                <span>{finding.message}</span>
+              </div>
+              <code className="text-xs text-muted-foreground break-all">{finding.command}</code>
+            </li>
+          ))}
+        </ul>
+        <AlertDialogFooter>
+          <AlertDialogCancel onClick={onReject}>Cancel</AlertDialogCancel>
+          <AlertDialogAction onClick={onApprove}>Run script</AlertDialogAction>
+        </AlertDialogFooter>
+      </AlertDialogContent>
+    </AlertDialog>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState, useCallback } from "react";
-import { downloadAndOpenDmg, type DmgDownloadEvent } from "../api/installation-commands";
+import { downloadAndOpenDmg } from "../api/installation-commands";
 
 export interface DmgDownloadState {
   isDownloading: boolean;
@@ -37,7 +37,8 @@ export function useDmgDownload(options?: {
     });
 
     try {
-      const filePath = await downloadAndOpenDmg(url, (event: DmgDownloadEvent) => {
+      const filePath = await downloadAndOpenDmg(url, (event) => {
+        if (event.type !== "progress") return;
         setState((prev) => ({
           ...prev,
           downloaded: event.downloaded,
@@ -19,7 +19,7 @@ export function useScriptInstall(options?: {
     success: false,
   });
 
-  const install = useCallback(async (commands: string[]) => {
+  const install = useCallback(async (templateId: string, methodIndex?: number) => {
     setState({
       isInstalling: true,
       output: [],
@@ -28,7 +28,7 @@ export function useScriptInstall(options?: {
     });
 
     try {
-      await installToolScript(commands, (event: ScriptInstallEvent) => {
+      await installToolScript(templateId, methodIndex, (event: ScriptInstallEvent) => {
         setState((prev) => {
           switch (event.type) {
             case "status":
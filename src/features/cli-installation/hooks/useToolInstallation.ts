@@ -1,8 +1,14 @@
-import { useCallback, useState } from "react";
+import { useCallback, useRef, useState } from "react";
 import { useBrewInstall } from "./useBrewInstall";
 import { useDmgDownload } from "./useDmgDownload";
 import { useScriptInstall } from "./useScriptInstall";
-import { installToolBrewFormula, installToolBrewTap } from "../api/installation-commands";
+import {
+  acknowledgeInstallScript,
+  analyzeInstallScript,
+  installToolBrewFormula,
+  installToolBrewTap,
+} from "../api/installation-commands";
+import type { ScriptReview } from "../components/ScriptReviewDialog";
 import type { InstallMethod } from "@/features/cli-management/types/cli-tool";
 
 export interface ToolInstallationState {
@@ -24,6 +30,31 @@ export function useToolInstallation(options?: {
     error: null,
     completedTools: [],
   });
+  const [scriptReview, setScriptReview] = useState<ScriptReview | null>(null);
+  const resolveReview = useRef<((approved: boolean) => void) | null>(null);
+
+  // Scripts with high-severity findings only run once the user has seen the
+  // findings and acknowledged them
+  const reviewScript = useCallback(async (toolId: string, methodIndex: number) => {
+    const analysis = await analyzeInstallScript(toolId, methodIndex);
+    const token = analysis.acknowledgementToken;
+    if (!token) return true;
+
+    const approved = await new Promise<boolean>((resolve) => {
+      resolveReview.current = resolve;
+      setScriptReview({ toolId, analysis });
+    });
+    setScriptReview(null);
+    resolveReview.current = null;
+    if (approved) {
+      await acknowledgeInstallScript(token);
+    }
+    return approved;
+  }, []);
+
+  const answerScriptReview = useCallback((approved: boolean) => {
+    resolveReview.current?.(approved);
+  }, []);
 
   const brewInstall = useBrewInstall({
     onSuccess: () => {
@@ -135,11 +166,20 @@ export function useToolInstallation(options?: {
         // Find the appropriate install method
         const brewMethod = tool.installMethods.find((m) => m.type === "brew");
         const dmgMethod = tool.installMethods.find((m) => m.type === "dmg");
-        const scriptMethod = tool.installMethods.find((m) => m.type === "script");
+        const scriptIndex = tool.installMethods.findIndex((m) => m.type === "script");
+        const scriptMethod = tool.installMethods[scriptIndex];
 
         try {
           if (scriptMethod?.scriptCommands) {
-            await scriptInstall.install(scriptMethod.scriptCommands);
+            if (!(await reviewScript(tool.id, scriptIndex))) {
+              setState((prev) => ({
+                ...prev,
+                isInstalling: false,
+                error: `Installation of ${tool.id} was cancelled`,
+              }));
+              return;
+            }
+            await scriptInstall.install(tool.id, scriptIndex);
             if (scriptInstall.error) {
               return;
             }
@@ -247,7 +287,7 @@ export function useToolInstallation(options?: {
         }
       }
     },
-    [brewInstall, dmgDownload, scriptInstall, options]
+    [brewInstall, dmgDownload, scriptInstall, reviewScript, options]
   );
 
   const reset = useCallback(() => {
@@ -281,5 +321,8 @@ export function useToolInstallation(options?: {
     dmgState: dmgDownload,
     scriptState: scriptInstall,
     logOutput: currentLogOutput(),
+    scriptReview,
+    approveScript: () => answerScriptReview(true),
+    rejectScript: () => answerScriptReview(false),
   };
 }
@@ -12,6 +12,7 @@ import { DependencyInstallPreview } from "@/features/cli-installation/components
 import { InstallationProgressDialog } from "@/features/cli-installation/components/InstallationProgressDialog";
 import { toast } from "sonner";
 import { useToolInstallation } from "@/features/cli-installation/hooks/useToolInstallation";
+import { ScriptReviewDialog } from "@/features/cli-installation/components/ScriptReviewDialog";
 import { CategoryFilter, type Category } from "@/components/CategoryFilter";
 import type { CliToolDetection } from "../types/cli-tool";
 
@@ -226,6 +227,11 @@ export function CliToolsPage() {
           toolInstallation.reset();
         }}
       />
+      <ScriptReviewDialog
+        review={toolInstallation.scriptReview}
+        onApprove={toolInstallation.approveScript}
+        onReject={toolInstallation.rejectScript}
+      />
     </div>
   );
 }
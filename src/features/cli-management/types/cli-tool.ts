@@ -1,11 +1,35 @@
 export interface InstallMethod {
-  type: "brew" | "dmg" | "script";
+  type:
+    | "brew"
+    | "dmg"
+    | "script"
+    | "github"
+    | "system"
+    | "npm"
+    | "pipx"
+    | "uv-tool"
+    | "cargo"
+    | "go"
+    | "runtime-manager";
   caskName?: string;
   formulaName?: string;
   brewTap?: string;
   dmgUrl?: string;
   dmgInstallSteps?: string[];
   scriptCommands?: string[];
+  githubRepo?: string;
+  githubAssetPattern?: string;
+  systemPackage?: string;
+  systemPackages?: Record<string, string>;
+  packageName?: string;
+  packageVersion?: string;
+  runtimeName?: string;
+  runtimeManager?: "mise" | "asdf";
+  uninstallCommands?: string[];
+  sha256?: string;
+  checksumUrl?: string;
+  checksumPattern?: string;
+  binaryPath?: string;
 }
 
 export interface ConfigFileLocation {
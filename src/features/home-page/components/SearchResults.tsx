@@ -6,6 +6,7 @@ import { SoftwareCard } from "@/features/software-recommendations/components/Sof
 import { useCliTemplates } from "@/features/cli-management/hooks/useCliTemplates";
 import { useDependencyAwareInstall } from "@/features/cli-installation/hooks/useDependencyAwareInstall";
 import { useToolInstallation } from "@/features/cli-installation/hooks/useToolInstallation";
+import { ScriptReviewDialog } from "@/features/cli-installation/components/ScriptReviewDialog";
 import { toast } from "sonner";
 import type { SearchResult } from "../types/search";
 import type { CliToolDetection } from "@/features/cli-management/types/cli-tool";
@@ -137,6 +138,11 @@ export function SearchResults({ results }: SearchResultsProps) {
           isInstalling={toolInstallation.isInstalling}
         />
       )}
+      <ScriptReviewDialog
+        review={toolInstallation.scriptReview}
+        onApprove={toolInstallation.approveScript}
+        onReject={toolInstallation.rejectScript}
+      />
     </div>
   );
 }
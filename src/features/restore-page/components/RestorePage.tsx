@@ -6,6 +6,7 @@ import type { RestoreItem } from "../types/restore";
 import { useCliDetection } from "@/features/cli-management/hooks/useCliDetection";
 import { useSoftwareInstallationStatus } from "@/features/software-recommendations/hooks/useSoftwareInstallationStatus";
 import { useToolInstallation } from "@/features/cli-installation/hooks/useToolInstallation";
+import { ScriptReviewDialog } from "@/features/cli-installation/components/ScriptReviewDialog";
 import { useDependencyAwareInstall } from "@/features/cli-installation/hooks/useDependencyAwareInstall";
 import { useBrewInstall } from "@/features/cli-installation/hooks/useBrewInstall";
 import { listCustomTemplates } from "@/features/cli-management/api/cli-commands";
@@ -461,6 +462,11 @@ export function RestorePage() {
           <p>No items to display</p>
         </div>
       )}
+      <ScriptReviewDialog
+        review={toolInstallation.scriptReview}
+        onApprove={toolInstallation.approveScript}
+        onReject={toolInstallation.rejectScript}
+      />
       </div>
     </>
   );
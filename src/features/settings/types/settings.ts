@@ -2,6 +2,13 @@ export interface AppSettings {
   downloadPath?: string | null;
   defaultEditor?: string | null;
   proxy?: ProxySettings | null;
+  privilegeEscalation?: "sudo" | "pkexec" | "none" | null;
+  installHistory?: InstallHistorySettings | null;
+}
+
+export interface InstallHistorySettings {
+  maxJobs?: number | null;
+  maxAgeDays?: number | null;
 }
 
 export interface ProxySettings {
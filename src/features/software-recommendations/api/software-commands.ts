@@ -2,8 +2,10 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
   SoftwareRecommendationsConfig,
   GitHubReleaseInfo,
+  AssetSelection,
   SoftwareRecommendation,
 } from "../types/software-recommendation";
+import type { DownloadVerificationEvent } from "@/features/cli-installation/api/installation-commands";
 
 export async function getSoftwareRecommendations(): Promise<SoftwareRecommendationsConfig> {
   return await invoke("get_software_recommendations");
@@ -27,20 +29,36 @@ export async function getGitHubLatestRelease(
   });
 }
 
+export async function selectGitHubReleaseAsset(
+  owner: string,
+  repo: string,
+  assetPattern?: string
+): Promise<AssetSelection> {
+  return await invoke("select_github_release_asset", {
+    owner,
+    repo,
+    assetPattern: assetPattern || null,
+  });
+}
+
 export interface GitHubDownloadEvent {
   type: "progress";
   downloaded: number;
   total: number;
+  resumedFrom: number;
   percentage: number;
 }
 
 export async function downloadGitHubReleaseAsset(
   url: string,
-  onProgress: (event: GitHubDownloadEvent) => void
+  onProgress: (event: GitHubDownloadEvent | DownloadVerificationEvent) => void,
+  checksum?: { sha256?: string; checksumUrl?: string; checksumPattern?: string },
+  /** Installs AppImage, .deb and .rpm downloads on Linux under this name */
+  appName?: string
 ): Promise<string> {
   const { listen } = await import("@tauri-apps/api/event");
 
-  const unlisten = await listen<GitHubDownloadEvent>(
+  const unlisten = await listen<GitHubDownloadEvent | DownloadVerificationEvent>(
     "github-download-progress",
     (event) => {
       onProgress(event.payload);
@@ -50,6 +68,8 @@ export async function downloadGitHubReleaseAsset(
   try {
     const filePath = await invoke<string>("download_github_release_asset", {
       url,
+      ...checksum,
+      appName: appName || null,
     });
     return filePath;
   } finally {
@@ -39,7 +39,7 @@ export function GitHubDownloadButton({
 
   const handleDownload = () => {
     if (release && release.assets.length > 0) {
-      download(release.assets[0].browser_download_url);
+      download(release.assets[0].browser_download_url, softwareName);
     }
   };
 
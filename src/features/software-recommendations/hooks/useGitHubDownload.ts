@@ -1,8 +1,5 @@
 import { useState, useCallback } from "react";
-import {
-  downloadGitHubReleaseAsset,
-  type GitHubDownloadEvent,
-} from "../api/software-commands";
+import { downloadGitHubReleaseAsset } from "../api/software-commands";
 
 export interface GitHubDownloadState {
   isDownloading: boolean;
@@ -29,7 +26,7 @@ export function useGitHubDownload(options?: {
   });
 
   const download = useCallback(
-    async (url: string) => {
+    async (url: string, appName?: string) => {
       setState({
         isDownloading: true,
         progress: 0,
@@ -43,14 +40,17 @@ export function useGitHubDownload(options?: {
       try {
         const filePath = await downloadGitHubReleaseAsset(
           url,
-          (event: GitHubDownloadEvent) => {
+          (event) => {
+            if (event.type !== "progress") return;
             setState((prev) => ({
               ...prev,
               downloaded: event.downloaded,
               total: event.total,
               progress: event.percentage,
             }));
-          }
+          },
+          undefined,
+          appName
         );
 
         setState({
@@ -11,6 +11,9 @@ export interface SoftwareInstallMethod {
   repo?: string;
   assetPattern?: string;
   url?: string;
+  sha256?: string;
+  checksumUrl?: string;
+  checksumPattern?: string;
 }
 
 export interface SoftwareRecommendation {
@@ -41,3 +44,18 @@ export interface GitHubReleaseInfo {
   published_at: string;
 }
 
+export interface AssetScore {
+  name: string;
+  url: string;
+  /** null when the asset can't be installed on this machine */
+  score: number | null;
+  reasons: string[];
+}
+
+export interface AssetSelection {
+  selected: string | null;
+  url: string | null;
+  explanation: string;
+  ranked: AssetScore[];
+}
+